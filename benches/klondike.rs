@@ -1,5 +1,70 @@
-use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
-use solitaire::variant::klondike::*;
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use solitaire::{seed, variant::klondike::*, GameState};
+use test_util::random::{self, Move};
+
+/// The seeds of the games benchmarked on, so results are comparable between runs
+const SEEDS: [u64; 8] = [0, 1, 2, 3, 4, 5, 6, 7];
+
+/// The number of moves played into each game, for realistic mid-game states
+const N_MOVES: usize = 20;
+
+/// Plays a move from `state` greedily, preferring moves to the foundations, then revealing a
+/// tableau card, then playing the talon onto the tableau, and otherwise drawing from the stock.
+/// Returns [None] if the move won the game or nothing can be played
+fn greedy_move(state: &PlayingGameState) -> Option<PlayingGameState> {
+    let playing = |r: Result<MoveResult>| match r {
+        Ok(MoveResult::Playing(next)) if &next != state => Some(next),
+        _ => None,
+    };
+    let tableau = || (0..NUM_TABLEAU).map(PileRef::Tableau);
+
+    [PileRef::Talon]
+        .into_iter()
+        .chain(tableau())
+        .find_map(|src| playing(GameRules::move_to_foundation(state.clone(), src)))
+        .or_else(|| {
+            tableau()
+                .filter(|&src| {
+                    let (face_down, _) = state.get_stack(src).unwrap().split_face_up();
+                    !face_down.is_empty()
+                })
+                .find_map(|src| {
+                    let take_n = movable_prefix(state.get_stack(src).unwrap());
+                    tableau().find_map(|dst| {
+                        playing(GameRules::move_cards(state.clone(), src, take_n, dst))
+                    })
+                })
+        })
+        .or_else(|| {
+            tableau().find_map(|dst| {
+                playing(GameRules::move_cards(state.clone(), PileRef::Talon, 1, dst))
+            })
+        })
+        .or_else(|| playing(GameRules::draw_stock(state.clone(), 1).map(MoveResult::Playing)))
+}
+
+/// A mid-game [PlayingGameState] for each of the [SEEDS], dealt from the seed and then
+/// played [N_MOVES] [greedy_move]s into, so the corpus only depends on this crate
+fn corpus() -> Vec<PlayingGameState> {
+    SEEDS
+        .iter()
+        .map(|&s| {
+            let mut state = GameRules::new_and_deal_with_rng(&mut seed::rng_from_u64(s));
+            for _ in 0..N_MOVES {
+                match greedy_move(&state) {
+                    Some(next) => state = next,
+                    None => break,
+                }
+            }
+            state
+        })
+        .collect()
+}
 
 pub fn bench_game_rules_deal_all(c: &mut Criterion) {
     let deck: Deck = Card::new_deck();
@@ -8,20 +73,109 @@ pub fn bench_game_rules_deal_all(c: &mut Criterion) {
     let mut group = c.benchmark_group("GameRules::deal_all");
     group.bench_function(BenchmarkId::new("deal_one", "Deck"), |b| {
         b.iter(|| {
-            let mut game_state = initial_game_state.clone();
+            let mut game_state = black_box(initial_game_state.clone());
             loop {
                 match GameRules::deal_one(game_state) {
                     DealResult::Dealing(new_state) => game_state = new_state,
-                    DealResult::Complete(new_state) => return new_state,
+                    DealResult::Complete(new_state) => return black_box(new_state),
                 }
             }
         })
     });
     group.bench_function(BenchmarkId::new("deal_all", "_"), |b| {
-        b.iter(|| GameRules::deal_all(initial_game_state.clone()))
+        b.iter(|| black_box(GameRules::deal_all(black_box(initial_game_state.clone()))))
     });
     group.finish();
 }
 
-criterion_group!(benches, bench_game_rules_deal_all);
+pub fn bench_legal_moves(c: &mut Criterion) {
+    let corpus = corpus();
+    c.bench_function("legal_moves", |b| {
+        b.iter(|| {
+            black_box(
+                corpus
+                    .iter()
+                    .map(|s| random::legal_moves(black_box(s)).len())
+                    .sum::<usize>(),
+            )
+        })
+    });
+}
+
+pub fn bench_game_rules_move_cards(c: &mut Criterion) {
+    // Every legal move from each state in the corpus
+    let moves: Vec<_> = corpus()
+        .into_iter()
+        .flat_map(|s| {
+            random::legal_moves(&s)
+                .into_iter()
+                .filter_map(move |(m, _)| match m {
+                    Move::MoveCards { src, take_n, dst } => Some((s.clone(), src, take_n, dst)),
+                    Move::DrawStock(_) => None,
+                })
+        })
+        .collect();
+
+    c.bench_function("GameRules::move_cards", |b| {
+        b.iter(|| {
+            black_box(
+                moves
+                    .iter()
+                    .filter(|(state, src, take_n, dst)| {
+                        GameRules::move_cards(black_box(state.clone()), *src, *take_n, *dst).is_ok()
+                    })
+                    .count(),
+            )
+        })
+    });
+}
+
+pub fn bench_game_rules_move_to_foundation(c: &mut Criterion) {
+    let corpus = corpus();
+    let srcs: Vec<_> = [PileRef::Talon]
+        .into_iter()
+        .chain((0..NUM_TABLEAU).map(PileRef::Tableau))
+        .collect();
+
+    c.bench_function("GameRules::move_to_foundation", |b| {
+        b.iter(|| {
+            black_box(
+                corpus
+                    .iter()
+                    .flat_map(|state| srcs.iter().map(move |&src| (state, src)))
+                    .filter(|&(state, src)| {
+                        GameRules::move_to_foundation(black_box(state.clone()), src).is_ok()
+                    })
+                    .count(),
+            )
+        })
+    });
+}
+
+pub fn bench_hash(c: &mut Criterion) {
+    let corpus = corpus();
+    c.bench_function("PlayingGameState::hash", |b| {
+        b.iter(|| {
+            black_box(
+                corpus
+                    .iter()
+                    .map(|s| {
+                        let mut hasher = DefaultHasher::new();
+                        black_box(s).hash(&mut hasher);
+                        hasher.finish()
+                    })
+                    .fold(0, |acc, h| acc ^ h),
+            )
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_game_rules_deal_all,
+    bench_legal_moves,
+    bench_game_rules_move_cards,
+    bench_game_rules_move_to_foundation,
+    bench_hash
+);
 criterion_main!(benches);
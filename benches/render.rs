@@ -0,0 +1,98 @@
+//! Frame times of the TUI. The TUI is a binary rather than part of the library,
+//! so its modules are included from the binary's source directly
+// Parts of the TUI only the binary uses, and its unit tests' imports, are unused here
+#![allow(dead_code, unused_imports)]
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{backend::TestBackend, Terminal};
+use solitaire::{seed, variant::klondike::*};
+
+use crate::{
+    component::{
+        game::{GameComponent, Settings},
+        Component,
+    },
+    event::Event,
+};
+
+#[path = "../src/bin/solitaire-tui"]
+mod tui {
+    pub mod component;
+    pub mod error;
+    pub mod event;
+    pub mod i18n;
+}
+use tui::{component, error, event, i18n};
+
+/// The size of the terminal rendered to, big enough for the longest pile
+const WIDTH: u16 = 120;
+const HEIGHT: u16 = 60;
+
+/// A [PlayingGameState] with every card face up in the first four tableau piles,
+/// each a full run from King down to Ace, so the piles are as long as they can get
+fn large_tableau() -> PlayingGameState {
+    let run = |even: FrenchSuit, odd: FrenchSuit| {
+        Rank::VALUES
+            .iter()
+            .enumerate()
+            .map(|(i, &rank)| Card {
+                suit: if i % 2 == 0 { even } else { odd },
+                rank,
+                face_up: true,
+            })
+            .collect::<Stack>()
+    };
+    PlayingGameState {
+        tableau: [
+            run(FrenchSuit::Spades, FrenchSuit::Hearts),
+            run(FrenchSuit::Hearts, FrenchSuit::Spades),
+            run(FrenchSuit::Clubs, FrenchSuit::Diamonds),
+            run(FrenchSuit::Diamonds, FrenchSuit::Clubs),
+            Stack::new(),
+            Stack::new(),
+            Stack::new(),
+        ],
+        foundations: [(); NUM_FOUNDATIONS].map(|_| Stack::new()),
+        stock: Stack::new(),
+        talon: Stack::new(),
+    }
+}
+
+pub fn bench_render(c: &mut Criterion) {
+    let mut group = c.benchmark_group("GameComponent::render");
+    for (name, ascii) in [("unicode", false), ("ascii", true)] {
+        let settings = Settings {
+            ascii,
+            ..Settings::default()
+        };
+        let mut game = GameComponent::from_state(seed::rng_from_u64(0), settings, large_tableau());
+        let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+
+        // Nothing changes between frames, so the render state is reused
+        group.bench_function(BenchmarkId::new("unchanged", name), |b| {
+            b.iter(|| {
+                terminal
+                    .draw(|f| black_box(&game).render(f, f.size()))
+                    .unwrap();
+            })
+        });
+
+        // The cursor moves every frame, so the render state is rebuilt
+        let mut keys = [KeyCode::Right, KeyCode::Left].into_iter().cycle();
+        group.bench_function(BenchmarkId::new("moving", name), |b| {
+            b.iter(|| {
+                let key = keys.next().unwrap();
+                game.handle_event(&Event::KeyPress(key, KeyModifiers::NONE))
+                    .unwrap();
+                terminal
+                    .draw(|f| black_box(&game).render(f, f.size()))
+                    .unwrap();
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_render);
+criterion_main!(benches);
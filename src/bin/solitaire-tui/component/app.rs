@@ -1,22 +1,103 @@
+use std::{fmt, time::Duration};
+
+use crossterm::event::{KeyCode, KeyModifiers};
 use ratatui::{layout::Rect, Frame};
 
 use crate::{
-    component::{game::GameComponent, *},
+    component::{
+        game::{feedback, format_duration, GameComponent, Settings, Summary},
+        *,
+    },
     error::Result,
-    event::Event,
+    event::{Event, EventState},
 };
 
 pub struct AppComponent<RNG: rand::Rng> {
     game: GameComponent<RNG>,
+    /// Whether the player asked to quit
+    quit: bool,
+    /// The games played so far, if playing a session of games in a row
+    session: Option<Session>,
+}
+
+/// Tracks a session of games played in a row
+#[derive(Default)]
+struct Session {
+    games: usize,
+    wins: usize,
+    /// The time until the next game is dealt, once the current game is finished
+    next_game_in: Option<Duration>,
+}
+
+/// How long a finished game stays on screen before the next game in a session is dealt
+const SESSION_NEXT_GAME_DELAY: Duration = Duration::from_secs(3);
+
+/// Summary of a session of games
+pub struct SessionSummary {
+    pub games: usize,
+    pub wins: usize,
+    /// The time each game had to be finished in, if the session was timed,
+    /// so timed results are kept apart from untimed ones
+    pub time_limit: Option<Duration>,
+}
+
+impl fmt::Display for SessionSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let percent = match self.games {
+            0 => 0,
+            games => self.wins * 100 / games,
+        };
+        if let Some(limit) = self.time_limit {
+            writeln!(f, "timed:  {}", format_duration(limit))?;
+        }
+        writeln!(f, "games:  {}", self.games)?;
+        write!(f, "won:    {} ({}%)", self.wins, percent)
+    }
 }
 
 impl<RNG: rand::Rng> Component for AppComponent<RNG> {
     fn handle_event(&mut self, event: &Event) -> EventResult {
-        self.game.handle_event(event)
+        // Shortcuts that always apply, whatever the game is doing
+        if let Event::KeyPress(KeyCode::Char('c'), KeyModifiers::CONTROL) = event {
+            return self.handle_quit();
+        }
+
+        let result = self.game.handle_event(event)?;
+        self.record_results();
+        match result {
+            EventState::Consumed => Ok(EventState::Consumed),
+            // Shortcuts that only apply if the game didn't want the event
+            EventState::NotConsumed => match event {
+                Event::KeyPress(KeyCode::Char('q'), _) => self.handle_quit(),
+                _ => Ok(EventState::NotConsumed),
+            },
+        }
     }
 
     fn handle_tick(&mut self, dt: &std::time::Duration) -> Result<()> {
-        self.game.handle_tick(dt)
+        self.game.handle_tick(dt)?;
+
+        if let Some(session) = &mut self.session {
+            // Games are counted once they're replaced, see record_results
+            match (self.game.summary(), session.next_game_in) {
+                // Just finished, so wait a moment before dealing the next game
+                (Some(_), None) => session.next_game_in = Some(SESSION_NEXT_GAME_DELAY),
+                (Some(_), Some(next_game_in)) => match next_game_in.checked_sub(*dt) {
+                    Some(next_game_in) if !next_game_in.is_zero() => {
+                        session.next_game_in = Some(next_game_in)
+                    }
+                    _ => {
+                        session.next_game_in = None;
+                        self.game.new_game();
+                    }
+                },
+                // The player restarted before the next game was dealt
+                (None, Some(_)) => session.next_game_in = None,
+                (None, None) => {}
+            }
+        }
+        self.record_results();
+        Ok(())
     }
 
     fn render(&self, f: &mut Frame, rect: Rect) {
@@ -25,9 +106,111 @@ impl<RNG: rand::Rng> Component for AppComponent<RNG> {
 }
 
 impl<RNG: rand::Rng + Clone> AppComponent<RNG> {
-    pub fn new(rng: &RNG) -> AppComponent<RNG> {
+    pub fn new(rng: &RNG, settings: Settings) -> AppComponent<RNG> {
         AppComponent {
-            game: GameComponent::new(rng.clone()),
+            game: GameComponent::new(rng.clone(), settings),
+            quit: false,
+            session: None,
         }
     }
+
+    /// Plays a session of games in a row, dealing the next game once each one is finished
+    pub fn start_session(&mut self) {
+        self.session = Some(Session::default());
+    }
+
+    /// Retrieves a [SessionSummary], if playing a session.
+    /// The current game is included if it's finished or has been played at all
+    pub fn session_summary(&self) -> Option<SessionSummary> {
+        let current = self.game.outcome();
+        self.session.as_ref().map(|s| SessionSummary {
+            games: s.games + current.is_some() as usize,
+            wins: s.wins + current.unwrap_or(false) as usize,
+            time_limit: self.game.time_limit(),
+        })
+    }
+
+    /// Registers a [feedback::Sink] with the game
+    pub fn add_feedback_sink(&mut self, sink: Box<dyn feedback::Sink>) {
+        self.game.add_feedback_sink(sink);
+    }
+
+    /// Retrieves a [Summary] of the current game, if it's finished
+    pub fn summary(&self) -> Option<Summary> {
+        self.game.summary()
+    }
+}
+
+impl<RNG: rand::Rng> AppComponent<RNG> {
+    /// Whether the player asked to quit, so the app should stop
+    pub fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// Counts the games the [GameComponent] has replaced towards the session, if playing one
+    fn record_results(&mut self) {
+        let results = self.game.take_results();
+        if let Some(session) = &mut self.session {
+            session.games += results.len();
+            session.wins += results.iter().filter(|&&won| won).count();
+        }
+    }
+
+    fn handle_quit(&mut self) -> EventResult {
+        self.quit = true;
+        Ok(EventState::Consumed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_counts_abandoned_games() -> Result<()> {
+        let mut app = AppComponent::new(&solitaire::seed::rng_from_u64(0), Settings::default());
+        app.start_session();
+        let key = |c| Event::KeyPress(KeyCode::Char(c), KeyModifiers::NONE);
+
+        // Skip dealing, then draw from the stock
+        app.handle_event(&key(' '))?;
+        app.handle_event(&key(' '))?;
+        let summary = app.session_summary().unwrap();
+        assert_eq!((summary.games, summary.wins), (1, 0));
+
+        // Restarting before the next tick still counts the game, but only once
+        app.handle_event(&key('r'))?;
+        let summary = app.session_summary().unwrap();
+        assert_eq!((summary.games, summary.wins), (1, 0));
+        app.handle_tick(&Duration::from_millis(100))?;
+        let summary = app.session_summary().unwrap();
+        assert_eq!((summary.games, summary.wins), (1, 0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_session_summary() {
+        let summary = SessionSummary {
+            games: 3,
+            wins: 2,
+            time_limit: None,
+        };
+        assert_eq!(summary.to_string(), "games:  3\nwon:    2 (66%)");
+        let summary = SessionSummary {
+            games: 0,
+            wins: 0,
+            time_limit: None,
+        };
+        assert_eq!(summary.to_string(), "games:  0\nwon:    0 (0%)");
+        // Timed sessions are labelled with their time limit
+        let summary = SessionSummary {
+            games: 2,
+            wins: 1,
+            time_limit: Some(Duration::from_secs(300)),
+        };
+        assert_eq!(
+            summary.to_string(),
+            "timed:  5:00\ngames:  2\nwon:    1 (50%)"
+        );
+    }
 }
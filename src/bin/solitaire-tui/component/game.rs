@@ -1,5 +1,8 @@
+mod announce;
+pub mod feedback;
 pub mod game;
 mod render;
+pub mod theme;
 mod ui_state;
 
 pub use game::*;
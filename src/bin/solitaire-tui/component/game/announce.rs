@@ -0,0 +1,174 @@
+use solitaire::variant::klondike;
+
+use crate::{component::game::render, i18n::Strings};
+
+/// Describes the change between two game states in words, so players using a screen reader
+/// can follow play, e.g. "moved 7♠ from tableau 3 to 8♥ on tableau 5, revealed K♦".
+/// Returns [None] if nothing worth announcing happened
+pub fn describe_change(
+    prev: &klondike::GameStateOption,
+    next: &klondike::GameStateOption,
+    strings: &Strings,
+    ascii: bool,
+) -> Option<String> {
+    match (prev, next) {
+        (klondike::GameStateOption::Playing(_), klondike::GameStateOption::Playing(_)) => {}
+        (klondike::GameStateOption::Playing(_), klondike::GameStateOption::Win(_)) => {
+            return Some(strings.announce_won.to_string())
+        }
+        _ => return None,
+    }
+
+    // Both states are playing, so have the same piles in the same order
+    let prev = klondike::describe_state(prev).piles;
+    let next = klondike::describe_state(next).piles;
+    let len = |p: &klondike::PileDescription| p.face_down + p.face_up.len();
+    let (prev_src, next_src) = prev.iter().zip(&next).find(|(p, n)| len(n) < len(p))?;
+    let (prev_dst, next_dst) = prev.iter().zip(&next).find(|(p, n)| len(n) > len(p))?;
+    let (src, dst) = (next_src.pile, next_dst.pile);
+
+    let mut parts = Vec::new();
+    match (src, dst) {
+        (klondike::PileRef::Stock, klondike::PileRef::Talon) => parts.push(
+            strings
+                .announce_drew
+                .replace("{card}", &card_name(next_dst.face_up.last()?, ascii)),
+        ),
+        (klondike::PileRef::Talon, klondike::PileRef::Stock) => {
+            parts.push(strings.announce_recycled.to_string())
+        }
+        _ => {
+            let take_n = len(next_dst) - len(prev_dst);
+            let card = card_name(&next_dst.face_up[next_dst.face_up.len() - take_n], ascii);
+            let dst_name = match prev_dst.face_up.last() {
+                Some(c) => strings
+                    .announce_onto
+                    .replace("{card}", &card_name(c, ascii))
+                    .replace("{pile}", &pile_name(dst, strings)),
+                None => pile_name(dst, strings),
+            };
+            let template = match take_n {
+                1 => strings.announce_moved,
+                _ => strings.announce_moved_many,
+            };
+            parts.push(
+                template
+                    .replace("{card}", &card)
+                    .replace("{n}", &(take_n - 1).to_string())
+                    .replace("{src}", &pile_name(src, strings))
+                    .replace("{dst}", &dst_name),
+            );
+
+            // Check if moving the cards turned over the card underneath
+            if next_src.face_down < prev_src.face_down {
+                if let Some(c) = next_src.face_up.last() {
+                    parts.push(
+                        strings
+                            .announce_revealed
+                            .replace("{card}", &card_name(c, ascii)),
+                    );
+                }
+            }
+        }
+    }
+
+    let announcement = parts.join(", ");
+    Some(match ascii {
+        true => render::to_ascii(&announcement),
+        false => announcement,
+    })
+}
+
+fn pile_name(p: klondike::PileRef, strings: &Strings) -> String {
+    match p {
+        klondike::PileRef::Tableau(n) => format!("{} {}", strings.pile_tableau, n + 1),
+        klondike::PileRef::Foundation(n) => format!("{} {}", strings.pile_foundation, n + 1),
+        klondike::PileRef::Stock => strings.pile_stock.to_string(),
+        klondike::PileRef::Talon => strings.pile_talon.to_string(),
+    }
+}
+
+fn card_name(c: &klondike::Card, ascii: bool) -> String {
+    format!(
+        "{}{}",
+        render::rank_to_str(c.rank).trim_end(),
+        render::suit_to_str(c.suit, ascii)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use solitaire::common::{Card, FrenchSuit::*, Rank::*};
+
+    use super::*;
+    use crate::i18n::Language;
+
+    fn card(rank: klondike::Rank, suit: klondike::FrenchSuit, face_up: bool) -> Card {
+        Card {
+            suit,
+            rank,
+            face_up,
+        }
+    }
+
+    fn playing() -> klondike::PlayingGameState {
+        klondike::PlayingGameState {
+            tableau: [
+                vec![card(King, Diamonds, false), card(Seven, Spades, true)],
+                vec![card(Eight, Hearts, true)],
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+            ],
+            foundations: [vec![], vec![], vec![], vec![]],
+            stock: vec![card(Ace, Clubs, false)],
+            talon: vec![],
+        }
+    }
+
+    fn describe(
+        prev: klondike::PlayingGameState,
+        next: klondike::PlayingGameState,
+    ) -> Option<String> {
+        describe_change(
+            &klondike::GameStateOption::Playing(prev),
+            &klondike::GameStateOption::Playing(next),
+            Language::English.strings(),
+            false,
+        )
+    }
+
+    #[test]
+    fn test_describe_move() {
+        let prev = playing();
+        let next = klondike::GameRules::move_cards(
+            prev.clone(),
+            klondike::PileRef::Tableau(0),
+            1,
+            klondike::PileRef::Tableau(1),
+        )
+        .unwrap();
+        let next = match next {
+            klondike::MoveResult::Playing(next) => next,
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            describe(prev, next),
+            Some("moved 7♠ from tableau 1 to 8♥ on tableau 2, revealed K♦".to_string())
+        );
+    }
+
+    #[test]
+    fn test_describe_draw() {
+        let prev = playing();
+        let next = klondike::GameRules::draw_stock(prev.clone(), 1).unwrap();
+        assert_eq!(describe(prev, next), Some("drew A♣".to_string()));
+    }
+
+    #[test]
+    fn test_describe_nothing() {
+        assert_eq!(describe(playing(), playing()), None);
+    }
+}
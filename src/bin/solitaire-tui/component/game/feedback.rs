@@ -0,0 +1,130 @@
+use std::io::{self, Write};
+
+use solitaire::variant::klondike;
+
+/// Something that happened in a game that the player can be given feedback on,
+/// such as with a sound
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Event {
+    /// Cards were moved onto the tableau or foundations
+    CardPlaced,
+    /// The player tried to make a move that isn't allowed
+    InvalidMove,
+    /// A face down card on the tableau was turned over
+    Reveal,
+    /// The game was won
+    Win,
+}
+
+/// Receives feedback [Event]s, registered with the
+/// [GameComponent](crate::component::game::GameComponent)
+pub trait Sink {
+    fn handle(&mut self, event: Event);
+}
+
+/// [Sink] that rings the terminal bell for invalid moves and wins
+pub struct Bell;
+
+impl Sink for Bell {
+    fn handle(&mut self, event: Event) {
+        match event {
+            Event::InvalidMove | Event::Win => {
+                // Not worth interrupting the game over
+                let mut stdout = io::stdout();
+                let _ = stdout.write_all(b"\x07").and_then(|_| stdout.flush());
+            }
+            Event::CardPlaced | Event::Reveal => {}
+        }
+    }
+}
+
+/// Works out the [Event]s from the change between two game states.
+/// `attempted` is whether the player tried to make a move, so an unchanged state is invalid
+pub fn events(
+    prev: &klondike::GameStateOption,
+    next: &klondike::GameStateOption,
+    attempted: bool,
+) -> Vec<Event> {
+    let (prev, next) = match (prev, next) {
+        (klondike::GameStateOption::Playing(prev), klondike::GameStateOption::Playing(next)) => {
+            (prev, next)
+        }
+        (klondike::GameStateOption::Playing(_), klondike::GameStateOption::Win(_)) => {
+            return vec![Event::CardPlaced, Event::Win]
+        }
+        _ => return Vec::new(),
+    };
+    if prev == next {
+        return match attempted {
+            true => vec![Event::InvalidMove],
+            false => Vec::new(),
+        };
+    }
+
+    let mut events = Vec::new();
+    let placed = prev.foundation_count() < next.foundation_count()
+        || prev
+            .tableau
+            .iter()
+            .zip(&next.tableau)
+            .any(|(p, n)| p.len() < n.len());
+    if placed {
+        events.push(Event::CardPlaced);
+    }
+    if prev.face_down_count() > next.face_down_count() {
+        events.push(Event::Reveal);
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use solitaire::variant::klondike::{GameStateOption, PileRef, PlayingGameState, Stack};
+    use test_util::parse;
+
+    use super::*;
+
+    fn playing() -> PlayingGameState {
+        PlayingGameState {
+            tableau: [
+                parse::cards(&["#KD", "7S"]),
+                parse::cards(&["8H"]),
+                Stack::new(),
+                Stack::new(),
+                Stack::new(),
+                Stack::new(),
+                Stack::new(),
+            ],
+            foundations: [(); klondike::NUM_FOUNDATIONS].map(|_| Stack::new()),
+            stock: parse::cards(&["#AC"]),
+            talon: Stack::new(),
+        }
+    }
+
+    #[test]
+    fn test_events_move() {
+        let prev = GameStateOption::from(playing());
+        let next = GameStateOption::from(
+            klondike::GameRules::move_cards(playing(), PileRef::Tableau(0), 1, PileRef::Tableau(1))
+                .unwrap(),
+        );
+        assert_eq!(
+            events(&prev, &next, true),
+            vec![Event::CardPlaced, Event::Reveal]
+        );
+    }
+
+    #[test]
+    fn test_events_draw() {
+        let prev = GameStateOption::from(playing());
+        let next = GameStateOption::from(klondike::GameRules::draw_stock(playing(), 1).unwrap());
+        assert_eq!(events(&prev, &next, true), vec![]);
+    }
+
+    #[test]
+    fn test_events_invalid() {
+        let state = GameStateOption::from(playing());
+        assert_eq!(events(&state, &state, true), vec![Event::InvalidMove]);
+        assert_eq!(events(&state, &state, false), vec![]);
+    }
+}
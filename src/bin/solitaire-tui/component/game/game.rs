@@ -1,36 +1,161 @@
-use std::time::Duration;
+use std::{cell::RefCell, cmp, fmt, mem, time::Duration};
 
 use crossterm::event::{KeyCode, KeyModifiers};
 use ratatui::{
     prelude::*,
     widgets::{
         block::{Position, Title},
-        Block, BorderType, Borders,
+        Block, BorderType, Borders, Paragraph,
     },
     Frame,
 };
-use solitaire::variant::klondike;
+use solitaire::{rules::Redeal, variant::klondike};
 
 use crate::{
     component::{
         game::{
-            render, ui_state,
-            ui_state::{DealingState, HoveringState, State, UIState},
+            announce, feedback, render,
+            theme::ColorDepth,
+            ui_state,
+            ui_state::{CommandState, DealingState, HoveringState, State, UIState},
         },
         Component,
     },
     error::Result,
     event::{Event, EventResult, EventState},
+    i18n::Language,
 };
 
+/// Player configurable options for a [GameComponent]
+#[derive(Copy, Clone)]
+pub struct Settings {
+    /// The number of cards drawn from the stock at a time
+    pub draw_n: usize,
+    /// How many times the talon can be turned over to deal through the stock again
+    pub redeal: Redeal,
+    /// Only render ASCII characters, for terminals without unicode support
+    pub ascii: bool,
+    /// Render with colors
+    pub color: bool,
+    /// The colors the terminal can display, when rendering with [colors](Settings::color)
+    pub color_depth: ColorDepth,
+    /// The time the game must be finished within, if playing against the clock
+    pub time_limit: Option<Duration>,
+    /// The language of the UI's text
+    pub language: Language,
+    /// Describe each move in a status line, for players using screen readers
+    pub announce: bool,
+    /// The time between each card being dealt
+    pub deal_interval: Duration,
+    /// Disable animations, such as dealing
+    pub reduced_motion: bool,
+    /// Automatically play the safe opening moves once the cards are dealt
+    pub auto_open: bool,
+    /// How runs taken from the tableau are checked, see [klondike::SeqStrategy]
+    pub seq_strategy: klondike::SeqStrategy,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            draw_n: 1,
+            redeal: Redeal::Unlimited,
+            ascii: false,
+            color: true,
+            color_depth: ColorDepth::Basic,
+            time_limit: None,
+            language: Language::English,
+            announce: false,
+            deal_interval: Duration::from_millis(100),
+            reduced_motion: false,
+            auto_open: false,
+            seq_strategy: klondike::SeqStrategy::Strict,
+        }
+    }
+}
+
 pub struct GameComponent<RNG: rand::Rng> {
     rng: RNG,
+    settings: Settings,
     state: klondike::GameStateOption,
     ui_state: UIState,
+    /// Time spent playing the current game
+    elapsed: Duration,
+    /// The number of moves made in the current game
+    moves: usize,
+    /// The number of times the talon has been turned over in the current game
+    redeals: usize,
+    /// A description of the last move, if [announcing](Settings::announce) moves
+    announcement: Option<String>,
+    /// Whether `t` was pressed, so the next digit goes to a tableau pile
+    goto_tableau: bool,
+    /// Whether to show the [klondike::CardCount] over the board
+    show_count: bool,
+    /// The last [render::GameState], so it's only rebuilt when the game or UI state changes
+    render_cache: RefCell<Option<RenderCache>>,
+    /// Where [feedback::Event]s are sent
+    feedback_sinks: Vec<Box<dyn feedback::Sink>>,
+    /// Whether each game replaced by [new_game](GameComponent::new_game) was won,
+    /// until they're [taken](GameComponent::take_results)
+    results: Vec<bool>,
+}
+
+/// A [render::GameState], along with the states and the area it was built for
+struct RenderCache {
+    state: klondike::GameStateOption,
+    ui_state: UIState,
+    rect: Rect,
+    render_state: render::GameState,
+}
+
+/// Summary of a finished game
+pub struct Summary {
+    pub won: bool,
+    pub elapsed: Duration,
+    pub moves: usize,
+    /// The time the game had to be finished in, if it was timed
+    pub time_limit: Option<Duration>,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "result: {}", if self.won { "won" } else { "lost" })?;
+        writeln!(f, "time:   {}", format_duration(self.elapsed))?;
+        if let Some(limit) = self.time_limit {
+            writeln!(f, "limit:  {}", format_duration(limit))?;
+        }
+        write!(f, "moves:  {}", self.moves)
+    }
 }
 
 impl<RNG: rand::Rng> Component for GameComponent<RNG> {
     fn handle_event(&mut self, event: &Event) -> EventResult {
+        // Out of time, so the only thing left to do is restart
+        if self.is_out_of_time() {
+            return match event {
+                Event::KeyPress(KeyCode::Char('r'), _) | Event::KeyPress(KeyCode::Char('R'), _) => {
+                    self.handle_reset()
+                }
+                _ => Ok(EventState::NotConsumed),
+            };
+        }
+
+        // The second keystroke of `t` + digit
+        if mem::take(&mut self.goto_tableau) {
+            if let Event::KeyPress(KeyCode::Char(c @ '1'..='9'), _) = event {
+                return self.handle_goto_tableau(c.to_digit(10).unwrap() as u8);
+            }
+        }
+
+        // Pile keys in command mode
+        if let UIState::Command(_) = self.ui_state {
+            if let Event::KeyPress(KeyCode::Char(c), _) = event {
+                if let Some(pile) = command_pile(*c) {
+                    return self.handle_command_pile(pile);
+                }
+            }
+        }
+
         match event {
             Event::KeyPress(KeyCode::Up, m)
             | Event::KeyPress(KeyCode::Char('w'), m)
@@ -55,8 +180,12 @@ impl<RNG: rand::Rng> Component for GameComponent<RNG> {
             Event::KeyPress(KeyCode::Enter, _) | Event::KeyPress(KeyCode::Char(' '), _) => {
                 self.handle_interact()
             }
-            Event::KeyPress(KeyCode::Char(c @ '1'..='9'), _) => {
-                self.handle_goto(c.to_digit(10).unwrap())
+            Event::KeyPress(KeyCode::Char(c @ '1'..='6'), _) => {
+                self.handle_goto(c.to_digit(10).unwrap() as u8)
+            }
+            Event::KeyPress(KeyCode::Char('t'), _) | Event::KeyPress(KeyCode::Char('T'), _) => {
+                self.goto_tableau = true;
+                Ok(EventState::Consumed)
             }
             Event::KeyPress(KeyCode::Char('c'), _) | Event::KeyPress(KeyCode::Char('C'), _) => {
                 self.handle_cancel()
@@ -64,82 +193,320 @@ impl<RNG: rand::Rng> Component for GameComponent<RNG> {
             Event::KeyPress(KeyCode::Char('r'), _) | Event::KeyPress(KeyCode::Char('R'), _) => {
                 self.handle_reset()
             }
+            Event::KeyPress(KeyCode::Char('m'), _)
+            | Event::KeyPress(KeyCode::Char('M'), _)
+            | Event::KeyPress(KeyCode::End, KeyModifiers::SHIFT) => self.handle_select_max(),
+            Event::KeyPress(KeyCode::Char('f'), _) | Event::KeyPress(KeyCode::Char('F'), _) => {
+                self.handle_to_foundation()
+            }
+            Event::KeyPress(KeyCode::Char(':'), _) => self.handle_command(),
+            Event::KeyPress(KeyCode::Char('k'), _) | Event::KeyPress(KeyCode::Char('K'), _) => {
+                self.show_count = !self.show_count;
+                Ok(EventState::Consumed)
+            }
             _ => Ok(EventState::NotConsumed),
         }
     }
 
     fn handle_tick(&mut self, dt: &Duration) -> Result<()> {
-        self.ui_state = self.ui_state.handle_tick(dt, &mut self.state);
+        match self.state {
+            // Only count time spent playing, and stop the clock once out of time
+            klondike::GameStateOption::Playing(_) if !self.is_out_of_time() => {
+                self.elapsed += *dt;
+            }
+            _ => {}
+        }
+        self.ui_state = self
+            .ui_state
+            .handle_tick(dt, &mut self.state, &self.settings);
         Ok(())
     }
 
     fn render(&self, f: &mut Frame, rect: Rect) {
+        let strings = self.settings.language.strings();
+        let help = match self.ui_state {
+            _ if self.is_out_of_time() => strings.help_out_of_time,
+            UIState::Dealing(_) => strings.help_dealing,
+            UIState::Hovering(pile) => match pile {
+                HoveringState::Stock => strings.help_stock,
+                HoveringState::Talon => strings.help_talon,
+                HoveringState::Foundation(_) => strings.help_foundation,
+                HoveringState::Tableau(_) => strings.help_tableau,
+            },
+            UIState::Selecting(_) => strings.help_selecting,
+            UIState::Moving(_) => strings.help_moving,
+            UIState::Command(_) => strings.help_command,
+        };
+
+        let clock = self.settings.time_limit.map(|limit| {
+            let remaining = limit.saturating_sub(self.elapsed);
+            // Round up, so the countdown only shows 0:00 once time is up
+            let remaining = Duration::from_secs(remaining.as_millis().div_ceil(1000) as u64);
+            format!("┤ {}: {} ├", strings.time_left, format_duration(remaining))
+        });
+
+        let clock = if self.settings.ascii {
+            clock.map(|c| render::to_ascii(&c))
+        } else {
+            clock
+        };
+        // Leave room for the corners of the border
+        let help = fit_help(
+            help,
+            rect.width.saturating_sub(2) as usize,
+            self.settings.ascii,
+        );
+
         let outer = Block::default()
             .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
-            .title("Klondike")
+            .title(strings.title)
+            .title(Title::from(clock.unwrap_or_default()).alignment(Alignment::Right))
             .title(
-                Title::from(format!(
-                    "┤ {} ├",
-                    match self.ui_state {
-                        UIState::Dealing(_) => "skip: ␣",
-                        UIState::Hovering(pile) => match pile {
-                            HoveringState::Stock => "navigate: ← ↑ ↓ → | draw: ␣ | [r]estart",
-                            HoveringState::Talon => {
-                                "navigate: ← ↑ ↓ → | move: ⇧ + ← ↑ ↓ → | [r]estart"
-                            }
-                            HoveringState::Foundation(_) => {
-                                "navigate: ← ↑ ↓ → | move: ⇧ + ← ↑ ↓ → | [r]estart"
-                            }
-                            HoveringState::Tableau(_) => {
-                                "navigate: ← ↑ ↓ → | move: ⇧ + ← → | take more: ⇧ + ↑ | [r]estart"
-                            }
-                        },
-                        UIState::Selecting(_) => {
-                            "take more: ⇧ + ↑ | take less: ↓ | move: ← → | [c]ancel | [r]estart"
-                        }
-                        UIState::Moving(_) => "move: ← ↑ ↓ → | place: ␣ | [c]ancel | [r]estart",
-                    }
-                ))
-                .position(Position::Bottom)
-                .alignment(Alignment::Left),
+                Title::from(help)
+                    .position(Position::Bottom)
+                    .alignment(Alignment::Left),
             );
+        let outer = if self.settings.ascii {
+            outer.border_set(render::ASCII_BORDER)
+        } else {
+            outer.border_type(BorderType::Rounded)
+        };
 
         let inner_rect = outer.inner(rect);
+        let inner_rect = if self.settings.announce {
+            // Reserve the bottom line as a status line for announcements
+            let [board, status] = *Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(1)])
+                .split(inner_rect)
+            else {
+                unreachable!()
+            };
+            let announcement = self.announcement.as_deref().unwrap_or_default();
+            f.render_widget(Paragraph::new(announcement), status);
+            board
+        } else {
+            inner_rect
+        };
 
-        render::GameState::from((&self.state, &self.ui_state)).render(f, inner_rect);
+        let mut cache = self.render_cache.borrow_mut();
+        match &*cache {
+            Some(c)
+                if c.rect == inner_rect && c.ui_state == self.ui_state && c.state == self.state => {
+            }
+            _ => {
+                *cache = Some(RenderCache {
+                    state: self.state.clone(),
+                    ui_state: self.ui_state,
+                    rect: inner_rect,
+                    render_state: render::GameState::from((
+                        &self.state,
+                        &self.ui_state,
+                        &self.settings,
+                    )),
+                })
+            }
+        }
+        cache.as_ref().unwrap().render_state.render(f, inner_rect);
+        if self.show_count {
+            let count = klondike::count_cards(&self.state);
+            render::render_card_count(&count, &self.settings, f, inner_rect);
+        }
 
         f.render_widget(outer, rect);
     }
 }
 
 impl<RNG: rand::Rng> GameComponent<RNG> {
-    pub fn new(rng: RNG) -> GameComponent<RNG> {
+    pub fn new(rng: RNG, settings: Settings) -> GameComponent<RNG> {
         let mut rng = rng;
         let state = klondike::InitialGameState::new_with_rng(&mut rng);
         GameComponent {
             rng,
+            settings,
             state: klondike::GameStateOption::from(state),
             ui_state: UIState::Dealing(DealingState::new()),
+            elapsed: Duration::ZERO,
+            moves: 0,
+            redeals: 0,
+            announcement: None,
+            goto_tableau: false,
+            show_count: false,
+            render_cache: RefCell::new(None),
+            feedback_sinks: Vec::new(),
+            results: Vec::new(),
         }
     }
 
+    /// Starts from a game that's already in play, hovering over the stock.
+    /// Only the render benchmark uses this for now
+    #[allow(dead_code)]
+    pub fn from_state(
+        rng: RNG,
+        settings: Settings,
+        state: klondike::PlayingGameState,
+    ) -> GameComponent<RNG> {
+        GameComponent {
+            state: klondike::GameStateOption::from(state),
+            ui_state: UIState::Hovering(HoveringState::Stock),
+            ..GameComponent::new(rng, settings)
+        }
+    }
+
+    /// Registers a [feedback::Sink] to send [feedback::Event]s to
+    pub fn add_feedback_sink(&mut self, sink: Box<dyn feedback::Sink>) {
+        self.feedback_sinks.push(sink);
+    }
+
+    /// Retrieves a [Summary] of the game, if it's finished
+    pub fn summary(&self) -> Option<Summary> {
+        let won = match self.state {
+            klondike::GameStateOption::Win(_) => true,
+            _ if self.is_out_of_time() => false,
+            _ => return None,
+        };
+        Some(Summary {
+            won,
+            // The clock can overrun the time limit by up to a tick
+            elapsed: self
+                .settings
+                .time_limit
+                .map_or(self.elapsed, |limit| cmp::min(self.elapsed, limit)),
+            moves: self.moves,
+            time_limit: self.settings.time_limit,
+        })
+    }
+
+    /// The time each game must be finished in, see [Settings::time_limit]
+    pub fn time_limit(&self) -> Option<Duration> {
+        self.settings.time_limit
+    }
+
+    fn is_out_of_time(&self) -> bool {
+        self.settings
+            .time_limit
+            .is_some_and(|limit| self.elapsed >= limit)
+    }
+
     fn handle_direction(
         &mut self,
         dir: ui_state::Direction,
         modifier: KeyModifiers,
     ) -> EventResult {
-        self.ui_state = self.ui_state.handle_direction(dir, modifier, &self.state);
+        self.ui_state = self
+            .ui_state
+            .handle_direction(dir, modifier, &self.state, &self.settings);
         Ok(EventState::Consumed)
     }
 
     fn handle_interact(&mut self) -> EventResult {
-        self.ui_state = self.ui_state.handle_interact(&mut self.state);
+        let prev_state = self.state.clone();
+        let redealing = self.is_redealing();
+        // Turning the talon over once the passes are used up is as invalid as a bad move
+        if redealing && !self.can_redeal() {
+            self.handle_state_change(prev_state, true);
+            return Ok(EventState::Consumed);
+        }
+        // Everything but dealing and drawing tries to move cards
+        let attempted = !matches!(
+            self.ui_state,
+            UIState::Dealing(_) | UIState::Hovering(HoveringState::Stock) | UIState::Command(_)
+        );
+        self.ui_state = self
+            .ui_state
+            .handle_interact(&mut self.state, &self.settings);
+        if redealing && prev_state != self.state {
+            self.redeals += 1;
+        }
+        self.handle_state_change(prev_state, attempted);
+        Ok(EventState::Consumed)
+    }
+
+    /// Whether interacting would turn the talon over, as the stock is empty
+    fn is_redealing(&self) -> bool {
+        match (&self.ui_state, &self.state) {
+            (UIState::Hovering(HoveringState::Stock), klondike::GameStateOption::Playing(play)) => {
+                play.stock.is_empty() && !play.talon.is_empty()
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the talon can be turned over again, see [Settings::redeal]
+    fn can_redeal(&self) -> bool {
+        match self.settings.redeal {
+            Redeal::None => false,
+            Redeal::Unlimited => true,
+            Redeal::Limited(n) => self.redeals < n,
+        }
+    }
+
+    fn handle_to_foundation(&mut self) -> EventResult {
+        let prev_state = self.state.clone();
+        let attempted = matches!(
+            self.ui_state,
+            UIState::Hovering(HoveringState::Talon | HoveringState::Tableau(_))
+        );
+        self.ui_state = self.ui_state.handle_to_foundation(&mut self.state);
+        self.handle_state_change(prev_state, attempted);
+        Ok(EventState::Consumed)
+    }
+
+    /// Updates the move count and announcement, if the game state has changed since `prev_state`,
+    /// and sends any [feedback::Event]s. `attempted` is whether the player tried to move cards
+    fn handle_state_change(&mut self, prev_state: klondike::GameStateOption, attempted: bool) {
+        for event in feedback::events(&prev_state, &self.state, attempted) {
+            for sink in &mut self.feedback_sinks {
+                sink.handle(event);
+            }
+        }
+
+        // Count anything that changes a game in progress as a move
+        match prev_state {
+            klondike::GameStateOption::Playing(_) if prev_state != self.state => self.moves += 1,
+            _ => {}
+        }
+        if self.settings.announce && prev_state != self.state {
+            self.announcement = announce::describe_change(
+                &prev_state,
+                &self.state,
+                self.settings.language.strings(),
+                self.settings.ascii,
+            );
+        }
+    }
+
+    fn handle_goto(&mut self, i: u8) -> EventResult {
+        self.ui_state = self.ui_state.handle_goto(i, &self.state);
+        Ok(EventState::Consumed)
+    }
+
+    fn handle_goto_tableau(&mut self, i: u8) -> EventResult {
+        self.ui_state = self.ui_state.handle_goto_tableau(i, &self.state);
         Ok(EventState::Consumed)
     }
 
-    fn handle_goto(&mut self, c: u32) -> EventResult {
-        self.ui_state = self.ui_state.handle_goto(c as u8);
+    fn handle_select_max(&mut self) -> EventResult {
+        self.ui_state = self.ui_state.handle_select_max(&self.state, &self.settings);
+        Ok(EventState::Consumed)
+    }
+
+    fn handle_command(&mut self) -> EventResult {
+        self.ui_state = self.ui_state.handle_command();
+        Ok(EventState::Consumed)
+    }
+
+    fn handle_command_pile(&mut self, pile: klondike::PileRef) -> EventResult {
+        let prev_state = self.state.clone();
+        // Typing the destination tries to move cards
+        let attempted = matches!(
+            self.ui_state,
+            UIState::Command(CommandState { src: Some(_), .. })
+        );
+        self.ui_state = self
+            .ui_state
+            .handle_command_pile(pile, &mut self.state, &self.settings);
+        self.handle_state_change(prev_state, attempted);
         Ok(EventState::Consumed)
     }
 
@@ -149,10 +516,75 @@ impl<RNG: rand::Rng> GameComponent<RNG> {
     }
 
     fn handle_reset(&mut self) -> EventResult {
+        self.new_game();
+        Ok(EventState::Consumed)
+    }
+
+    /// Whether the current game was won, if it's finished or has been played at all.
+    /// A game that isn't finished counts as lost, as it'd be abandoned if it ended now
+    pub fn outcome(&self) -> Option<bool> {
+        match self.summary() {
+            Some(summary) => Some(summary.won),
+            None if self.moves > 0 => Some(false),
+            None => None,
+        }
+    }
+
+    /// Takes the results of the games replaced since the last call, see [outcome](Self::outcome)
+    pub fn take_results(&mut self) -> Vec<bool> {
+        mem::take(&mut self.results)
+    }
+
+    /// Abandons the current game and deals a new one
+    pub fn new_game(&mut self) {
+        if let Some(won) = self.outcome() {
+            self.results.push(won);
+        }
         self.state = klondike::GameStateOption::from(klondike::InitialGameState::new_with_rng(
             &mut self.rng,
         ));
         self.ui_state = UIState::Dealing(DealingState::new());
-        Ok(EventState::Consumed)
+        self.elapsed = Duration::ZERO;
+        self.moves = 0;
+        self.redeals = 0;
+        self.announcement = None;
+    }
+}
+
+/// Maps a key to a pile in command mode: `1`-`7` for the tableau,
+/// `0` for the talon and `f` for whichever foundation will take the card
+fn command_pile(c: char) -> Option<klondike::PileRef> {
+    match c {
+        '0' => Some(klondike::PileRef::Talon),
+        'f' | 'F' => Some(klondike::PileRef::Foundation(0)),
+        c => match c.to_digit(10)? as usize {
+            n @ 1..=klondike::NUM_TABLEAU => Some(klondike::PileRef::Tableau(n - 1)),
+            _ => None,
+        },
     }
 }
+
+/// Wraps the help text in a title, dropping sections from the end until it fits within `width`.
+/// The width is measured in terminal columns rather than bytes, as translations aren't
+/// necessarily ASCII
+fn fit_help(help: &str, width: usize, ascii: bool) -> String {
+    let sections: Vec<_> = help.split(" | ").collect();
+    (1..=sections.len())
+        .rev()
+        .map(|n| {
+            let title = format!("┤ {} ├", sections[..n].join(" | "));
+            if ascii {
+                render::to_ascii(&title)
+            } else {
+                title
+            }
+        })
+        .find(|title| Span::raw(title).width() <= width)
+        .unwrap_or_default()
+}
+
+/// Formats the given [Duration] as minutes and seconds, e.g. `4:05`
+pub fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs();
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
@@ -1,19 +1,38 @@
-use std::collections::HashMap;
+use std::{cmp, collections::HashMap};
 
 use ratatui::{layout::Rect, prelude::*, symbols::*, text::Text, widgets::*, Frame};
-use solitaire::{variant::klondike, GameState as GameStateTrait};
+use solitaire::{variant::klondike, GameStateExt};
 
-use crate::component::game::ui_state::{MovingState, SelectingState, UIState};
+use crate::component::game::{
+    theme::Theme,
+    ui_state::{CommandState, MovingState, SelectingState, UIState},
+    Settings,
+};
 
 const CARD_WIDTH: u16 = 10;
 const CARD_HEIGHT: u16 = 7;
 
+/// [border::Set] for terminals without box drawing characters
+pub const ASCII_BORDER: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
 /// The render states a card can be in
 #[derive(Copy, Clone, Eq, PartialEq)]
 enum CardState {
     Normal,
     Selected,
-    Moving,
+    /// Previewed at the destination of a move, and whether the move is valid
+    Moving {
+        valid: bool,
+    },
 }
 
 /// A [Card](klondike::Card) with its [CardState] for rendering
@@ -22,71 +41,85 @@ type Card = (klondike::Card, CardState);
 /// Represents a GameState as it should be rendered
 pub struct GameState {
     piles: HashMap<klondike::PileRef, (Vec<Card>, bool)>,
+    settings: Settings,
 }
 
-impl From<(&klondike::GameStateOption, &UIState)> for GameState {
-    fn from((game_state, ui_state): (&klondike::GameStateOption, &UIState)) -> Self {
-        let pile_refs = [klondike::PileRef::Stock, klondike::PileRef::Talon]
-            .iter()
-            .cloned()
-            .chain(
-                [(); klondike::NUM_FOUNDATIONS]
-                    .iter()
-                    .enumerate()
-                    .map(|(i, _)| klondike::PileRef::Foundation(i)),
-            )
-            .chain(
-                [(); klondike::NUM_TABLEAU]
-                    .iter()
-                    .enumerate()
-                    .map(|(i, _)| klondike::PileRef::Tableau(i)),
-            )
-            .collect::<Vec<_>>();
-
-        let mut piles = HashMap::with_capacity(pile_refs.len());
-
-        for p in pile_refs {
-            let is_selected = match ui_state {
-                UIState::Hovering(pile_ref) => pile_ref == &p,
-                _ => false,
-            };
-            let stack = game_state.get_stack(p).map_or_else(
-                || Vec::new(),
-                |s| s.iter().cloned().map(|c| (c, CardState::Normal)).collect(),
-            );
-            piles.insert(p, (stack, is_selected));
+impl From<(&klondike::GameStateOption, &UIState, &Settings)> for GameState {
+    fn from(
+        (game_state, ui_state, settings): (&klondike::GameStateOption, &UIState, &Settings),
+    ) -> Self {
+        let mut piles: HashMap<klondike::PileRef, (Vec<Card>, bool)> = HashMap::new();
+        for (p, _, c) in game_state.cards() {
+            piles.entry(p).or_default().0.push((*c, CardState::Normal));
+        }
+        let selected = match ui_state {
+            UIState::Hovering(pile_ref) => Some(*pile_ref),
+            UIState::Command(CommandState { hovering, src }) => Some(src.unwrap_or(*hovering)),
+            _ => None,
+        };
+        if let Some(p) = selected {
+            piles.entry(p).or_default().1 = true;
         }
 
         // Set the CardState correctly
         match ui_state {
             UIState::Dealing(_) => {}
             UIState::Hovering(pile_ref) => {
-                let (ref mut pile, _) = piles.get_mut(&pile_ref).unwrap();
+                let (ref mut pile, _) = piles.entry(*pile_ref).or_default();
+                match pile.last_mut() {
+                    Some((_, s)) => *s = CardState::Selected,
+                    _ => {}
+                }
+            }
+            // Highlight the source pile once it's been typed
+            UIState::Command(CommandState {
+                src: Some(pile_ref),
+                ..
+            }) => {
+                let (ref mut pile, _) = piles.entry(*pile_ref).or_default();
                 match pile.last_mut() {
                     Some((_, s)) => *s = CardState::Selected,
                     _ => {}
                 }
             }
+            UIState::Command(_) => {}
             UIState::Selecting(SelectingState::Tableau { pile_n, take_n }) => {
-                let (ref mut pile, _) =
-                    piles.get_mut(&klondike::PileRef::Tableau(*pile_n)).unwrap();
+                let (ref mut pile, _) = piles
+                    .entry(klondike::PileRef::Tableau(*pile_n))
+                    .or_default();
                 let pile_len = pile.len();
                 for (_, s) in &mut pile[pile_len - take_n..pile_len] {
                     *s = CardState::Selected;
                 }
             }
             UIState::Moving(MovingState { src, take_n, dst }) => {
-                let (ref mut src, _) = piles.get_mut(&src).unwrap();
+                let valid = match game_state {
+                    klondike::GameStateOption::Playing(play) => {
+                        klondike::GameRules::move_cards_with(
+                            settings.seq_strategy,
+                            play.clone(),
+                            *src,
+                            *take_n,
+                            *dst,
+                        )
+                        .is_ok()
+                    }
+                    _ => false,
+                };
+                let (ref mut src, _) = piles.entry(*src).or_default();
                 let mut take = solitaire::take_n_vec_mut(src, *take_n);
                 for (_, s) in &mut take {
-                    *s = CardState::Moving;
+                    *s = CardState::Moving { valid };
                 }
-                let (ref mut dst, _) = piles.get_mut(&dst).unwrap();
+                let (ref mut dst, _) = piles.entry(*dst).or_default();
                 *dst = dst.iter().chain(take.iter()).cloned().collect()
             }
         }
 
-        GameState { piles }
+        GameState {
+            piles,
+            settings: *settings,
+        }
     }
 }
 
@@ -146,35 +179,123 @@ impl GameState {
         }
     }
 
+    /// The cards of the pile `p` and whether it's selected.
+    /// Piles that aren't in the game state, e.g. the foundations while dealing, are empty
+    fn pile(&self, p: klondike::PileRef) -> (&[Card], bool) {
+        match self.piles.get(&p) {
+            Some((pile, is_selected)) => (pile, *is_selected),
+            None => (&[], false),
+        }
+    }
+
     fn render_stock(&self, f: &mut Frame, rect: Rect) {
-        let (pile, is_selected) = self.piles.get(&klondike::PileRef::Stock).unwrap();
+        let (pile, is_selected) = self.pile(klondike::PileRef::Stock);
 
-        render_card(pile.last(), *is_selected, border::ROUNDED, f, rect);
+        self.render_card(pile.last(), is_selected, self.border_set(), f, rect);
+
+        // Drawing from an empty stock turns the talon over, so show that it can be recycled
+        let (talon, _) = self.pile(klondike::PileRef::Talon);
+        if pile.is_empty() && !talon.is_empty() {
+            let glyph = match self.settings.ascii {
+                true => "@",
+                false => "↻",
+            };
+            self.render_placeholder(glyph, f, rect);
+        }
     }
 
     fn render_talon(&self, f: &mut Frame, rect: Rect) {
-        let (pile, is_selected) = self.piles.get(&klondike::PileRef::Talon).unwrap();
+        let (pile, is_selected) = self.pile(klondike::PileRef::Talon);
+
+        // Fan out the most recently drawn cards, with the top card on the right
+        let fan_n = cmp::max(cmp::min(self.settings.draw_n, pile.len()), 1);
+        let offset = match fan_n {
+            1 => 0,
+            _ => {
+                rect.width.checked_sub(CARD_WIDTH).unwrap_or(0) / (self.settings.draw_n as u16 - 1)
+            }
+        };
 
-        // todo handle 3 card draw
+        if pile.is_empty() {
+            let rect = Rect {
+                width: CARD_WIDTH,
+                ..rect
+            };
+            return self.render_card(None, is_selected, self.border_set(), f, rect);
+        }
 
-        let rx_padding = rect.width.checked_sub(CARD_WIDTH).unwrap_or(0);
+        for (i, c) in pile[pile.len() - fan_n..].iter().enumerate() {
+            let border_set: border::Set = if i != 0 && !self.settings.ascii {
+                border::Set {
+                    top_left: line::HORIZONTAL_DOWN,
+                    bottom_left: line::HORIZONTAL_UP,
+                    ..border::ROUNDED
+                }
+            } else {
+                self.border_set()
+            };
 
-        let rect = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Length(CARD_WIDTH), Constraint::Min(rx_padding)])
-            .split(rect);
+            let rect = Rect {
+                x: rect.x + offset * i as u16,
+                width: CARD_WIDTH,
+                ..rect
+            };
 
-        render_card(pile.last(), *is_selected, border::ROUNDED, f, rect[0]);
+            // Only the top card shows as selected
+            self.render_card(Some(c), is_selected && i == fan_n - 1, border_set, f, rect);
+        }
     }
 
     fn render_foundation(&self, i: usize, f: &mut Frame, rect: Rect) {
-        let (pile, is_selected) = self.piles.get(&klondike::PileRef::Foundation(i)).unwrap();
+        let (pile, is_selected) = self.pile(klondike::PileRef::Foundation(i));
+
+        self.render_card(pile.last(), is_selected, self.border_set(), f, rect);
+
+        if pile.is_empty() {
+            self.render_foundation_placeholder(f, rect);
+        }
+    }
+
+    /// Marks an empty foundation with the suits it can accept.
+    /// Any foundation can take any suit, so long as another foundation hasn't started it
+    fn render_foundation_placeholder(&self, f: &mut Frame, rect: Rect) {
+        let started: Vec<_> = (0..klondike::NUM_FOUNDATIONS)
+            .filter_map(|i| {
+                let (pile, _) = self.pile(klondike::PileRef::Foundation(i));
+                pile.first().map(|(c, _)| c.suit)
+            })
+            .collect();
+        let suits = klondike::FrenchSuit::iter()
+            .filter(|s| !started.contains(s))
+            .map(|s| suit_to_str(s, self.settings.ascii))
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.render_placeholder(&suits, f, rect);
+    }
 
-        render_card(pile.last(), *is_selected, border::ROUNDED, f, rect);
+    /// Renders dimmed `text` in the middle of an empty card outline
+    fn render_placeholder(&self, text: &str, f: &mut Frame, rect: Rect) {
+        // The middle row of the card, inside the border
+        let rect = Rect {
+            x: rect.x + 1,
+            y: rect.y + rect.height / 2,
+            width: rect.width.saturating_sub(2),
+            height: cmp::min(rect.height, 1),
+        };
+        let style = match self.settings.color {
+            true => Style::default().fg(self.theme().placeholder),
+            false => Style::default().add_modifier(Modifier::DIM),
+        };
+        f.render_widget(
+            Paragraph::new(text)
+                .style(style)
+                .alignment(Alignment::Center),
+            rect,
+        );
     }
 
     fn render_tableau(&self, i: usize, f: &mut Frame, rect: Rect) {
-        let (pile, is_selected) = self.piles.get(&klondike::PileRef::Tableau(i)).unwrap();
+        let (pile, is_selected) = self.pile(klondike::PileRef::Tableau(i));
 
         if pile.is_empty() {
             let by_padding = rect.height.checked_sub(CARD_HEIGHT).unwrap_or(0);
@@ -184,19 +305,19 @@ impl GameState {
                 .constraints([Constraint::Length(CARD_HEIGHT), Constraint::Min(by_padding)])
                 .split(rect);
 
-            return render_card(None, *is_selected, border::ROUNDED, f, rect[0]);
+            return self.render_card(None, is_selected, self.border_set(), f, rect[0]);
         }
 
         let mut ty_padding = 0;
         for (i, &(c, s)) in pile.iter().enumerate() {
-            let border_set: border::Set = if i != 0 {
+            let border_set: border::Set = if i != 0 && !self.settings.ascii {
                 border::Set {
                     top_left: line::VERTICAL_RIGHT,
                     top_right: line::VERTICAL_LEFT,
                     ..border::ROUNDED
                 }
             } else {
-                border::ROUNDED
+                self.border_set()
             };
 
             let by_padding = rect
@@ -213,7 +334,7 @@ impl GameState {
                 ])
                 .split(rect);
 
-            render_card(Some(&(c, s)), false, border_set, f, rect[1]);
+            self.render_card(Some(&(c, s)), false, border_set, f, rect[1]);
 
             // Add 2 to the padding if the card is face up so the suit and rank are visible
             if c.face_up {
@@ -225,62 +346,171 @@ impl GameState {
             }
         }
     }
+
+    fn theme(&self) -> &'static Theme {
+        self.settings.color_depth.theme()
+    }
+
+    fn border_set(&self) -> border::Set {
+        match self.settings.ascii {
+            true => ASCII_BORDER,
+            false => border::ROUNDED,
+        }
+    }
+
+    fn render_card(
+        &self,
+        card: Option<&Card>,
+        is_selected: bool,
+        border_set: border::Set,
+        f: &mut Frame,
+        rect: Rect,
+    ) {
+        let state = card.map(|(_, s)| s).unwrap_or_else(|| {
+            if is_selected {
+                &CardState::Selected
+            } else {
+                &CardState::Normal
+            }
+        });
+
+        // Without colors, use modifiers to highlight the border instead
+        let border_style = match (state, self.settings.color) {
+            (CardState::Selected, true) => Style::default().fg(self.theme().selected),
+            (CardState::Moving { valid: true }, true) => {
+                Style::default().fg(self.theme().move_valid)
+            }
+            (CardState::Moving { valid: false }, true) => {
+                Style::default().fg(self.theme().move_invalid)
+            }
+            (CardState::Selected, false) => Style::default().add_modifier(Modifier::REVERSED),
+            (CardState::Moving { valid: true }, false) => {
+                Style::default().add_modifier(Modifier::BOLD)
+            }
+            (CardState::Moving { valid: false }, false) => {
+                Style::default().add_modifier(Modifier::CROSSED_OUT)
+            }
+            (CardState::Normal, _) => Style::default(),
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(border_set)
+            .border_style(border_style);
+
+        let inner_rect = block.inner(rect);
+
+        // Cards being moved are a preview, so are faded
+        let face_style = match state {
+            CardState::Moving { .. } => Style::default().add_modifier(Modifier::DIM),
+            _ => Style::default(),
+        };
+
+        match card {
+            Some((c, _)) => match c.face_up {
+                true => {
+                    // Each row has its own background, so the face can be shaded
+                    let lines: Vec<_> = card_to_str(c, self.settings.ascii, inner_rect)
+                        .lines()
+                        .zip(0..)
+                        .map(|(line, row)| {
+                            Line::styled(
+                                line.to_string(),
+                                match self.settings.color {
+                                    true => face_style
+                                        .bg(self.theme().face_bg_row(row, inner_rect.height))
+                                        .fg(card_to_color(c, self.theme())),
+                                    false => face_style,
+                                },
+                            )
+                        })
+                        .collect();
+                    f.render_widget(Paragraph::new(lines).block(block), rect)
+                }
+                false => f.render_widget(
+                    Paragraph::new(Text::styled(
+                        card_back_str(inner_rect),
+                        match self.settings.color {
+                            true => Style::default()
+                                .bg(self.theme().back_bg)
+                                .fg(self.theme().back_fg),
+                            false => Style::default(),
+                        },
+                    ))
+                    .block(block),
+                    rect,
+                ),
+            },
+            None => f.render_widget(block, rect),
+        }
+    }
 }
 
-fn render_card(
-    card: Option<&Card>,
-    is_selected: bool,
-    border_set: border::Set,
+/// Renders a [klondike::CardCount] over the board, as a grid of suits by ranks
+/// with a [klondike::CardStatus] marker for each card
+pub fn render_card_count(
+    count: &klondike::CardCount,
+    settings: &Settings,
     f: &mut Frame,
     rect: Rect,
 ) {
-    let state = card.map(|(_, s)| s).unwrap_or_else(|| {
-        if is_selected {
-            &CardState::Selected
-        } else {
-            &CardState::Normal
-        }
-    });
+    let strings = settings.language.strings();
+    let ranks = klondike::Rank::VALUES.iter().rev();
+
+    let mut lines = vec![format!(
+        "  {}",
+        ranks
+            .clone()
+            .map(|r| rank_to_str(*r))
+            .collect::<Vec<_>>()
+            .join(" ")
+    )];
+    for suit in klondike::FrenchSuit::iter() {
+        let markers = ranks.clone().map(|r| match count.get(suit, *r) {
+            klondike::CardStatus::Foundation => "f ",
+            klondike::CardStatus::Visible => "v ",
+            klondike::CardStatus::Unseen => "? ",
+        });
+        lines.push(format!(
+            "{} {}",
+            suit_to_str(suit, settings.ascii),
+            markers.collect::<Vec<_>>().join(" ")
+        ));
+    }
+    lines.push(String::new());
+    lines.push(strings.count_legend.to_string());
+    let title = format!(" {} ", strings.count_title);
+    let (lines, title) = match settings.ascii {
+        true => (
+            lines.iter().map(|l| to_ascii(l)).collect(),
+            to_ascii(&title),
+        ),
+        false => (lines, title),
+    };
 
-    let border_color = if state == &CardState::Selected {
-        Color::LightGreen
-    } else if state == &CardState::Moving {
-        Color::LightYellow
-    } else {
-        Color::default()
+    // Centred on the board, with a space either side of the text
+    let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0) as u16 + 4;
+    let height = lines.len() as u16 + 2;
+    let rect = Rect {
+        x: rect.x + rect.width.saturating_sub(width) / 2,
+        y: rect.y + rect.height.saturating_sub(height) / 2,
+        width: cmp::min(width, rect.width),
+        height: cmp::min(height, rect.height),
     };
 
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .border_set(border_set)
-        .fg(border_color);
-
-    let inner_rect = block.inner(rect);
-
-    match card {
-        Some((c, _)) => match c.face_up {
-            true => f.render_widget(
-                Paragraph::new(Text::styled(
-                    card_to_str(c, inner_rect),
-                    Style::default().bg(Color::White).fg(card_to_color(c)),
-                ))
-                .block(block),
-                rect,
-            ),
-            false => f.render_widget(
-                Paragraph::new(Text::styled(
-                    card_back_str(inner_rect),
-                    Style::default().bg(Color::Red).fg(Color::LightRed),
-                ))
-                .block(block),
-                rect,
-            ),
-        },
-        None => f.render_widget(block, rect),
-    }
+    let block = Block::default().borders(Borders::ALL).title(title);
+    let block = match settings.ascii {
+        true => block.border_set(ASCII_BORDER),
+        false => block.border_type(BorderType::Rounded),
+    };
+    f.render_widget(Clear, rect);
+    f.render_widget(
+        Paragraph::new(lines.join("\n")).block(block.padding(Padding::horizontal(1))),
+        rect,
+    );
 }
 
-fn rank_to_str(r: klondike::Rank) -> String {
+pub(super) fn rank_to_str(r: klondike::Rank) -> String {
     format!(
         "{:<2}",
         match r {
@@ -301,20 +531,24 @@ fn rank_to_str(r: klondike::Rank) -> String {
     )
 }
 
-fn suit_to_str(s: klondike::FrenchSuit) -> &'static str {
-    match s {
-        klondike::FrenchSuit::Clubs => "♣",
-        klondike::FrenchSuit::Spades => "♠",
-        klondike::FrenchSuit::Hearts => "♥",
-        klondike::FrenchSuit::Diamonds => "♦",
+pub(super) fn suit_to_str(s: klondike::FrenchSuit, ascii: bool) -> &'static str {
+    match (s, ascii) {
+        (klondike::FrenchSuit::Clubs, false) => "♣",
+        (klondike::FrenchSuit::Spades, false) => "♠",
+        (klondike::FrenchSuit::Hearts, false) => "♥",
+        (klondike::FrenchSuit::Diamonds, false) => "♦",
+        (klondike::FrenchSuit::Clubs, true) => "C",
+        (klondike::FrenchSuit::Spades, true) => "S",
+        (klondike::FrenchSuit::Hearts, true) => "H",
+        (klondike::FrenchSuit::Diamonds, true) => "D",
     }
 }
 
-fn card_to_str(c: &klondike::Card, rect: Rect) -> String {
+fn card_to_str(c: &klondike::Card, ascii: bool, rect: Rect) -> String {
     (0..rect.height)
         .map(|i| {
             let r = rank_to_str(c.rank);
-            let s = suit_to_str(c.suit);
+            let s = suit_to_str(c.suit, ascii);
             if i == 0 {
                 format!("{}{:>w$}", s, r, w = rect.width as usize - 1)
             } else if i == rect.height - 1 {
@@ -334,10 +568,36 @@ fn card_back_str(rect: Rect) -> String {
         .join("\n")
 }
 
-fn card_to_color(c: &klondike::Card) -> Color {
+fn card_to_color(c: &klondike::Card, theme: &Theme) -> Color {
     if c.suit.color() == klondike::Color::Red {
-        Color::Red
+        theme.red_suit
     } else {
-        Color::DarkGray
+        theme.black_suit
+    }
+}
+
+/// Replaces the non-ASCII symbols used in the UI with ASCII equivalents
+pub fn to_ascii(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '←' => out.push('<'),
+            '↑' => out.push('^'),
+            '↓' => out.push('v'),
+            '→' => out.push('>'),
+            '␣' => out.push_str("space"),
+            '⇧' => out.push_str("shift"),
+            '┤' | '├' => out.push('|'),
+            // Strip accents from translated text
+            'à' | 'â' => out.push('a'),
+            'ç' => out.push('c'),
+            'é' | 'è' | 'ê' | 'ë' => out.push('e'),
+            'î' | 'ï' => out.push('i'),
+            'ô' => out.push('o'),
+            'ù' | 'û' => out.push('u'),
+            c if !c.is_ascii() => out.push('?'),
+            c => out.push(c),
+        }
     }
+    out
 }
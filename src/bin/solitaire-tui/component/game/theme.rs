@@ -0,0 +1,154 @@
+use std::env;
+
+use ratatui::style::Color;
+
+/// The colors a terminal can display
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum ColorDepth {
+    /// The 16 basic ANSI colors
+    #[default]
+    #[value(name = "16")]
+    Basic,
+    /// The 256 color palette
+    #[value(name = "256")]
+    Indexed,
+    /// 24-bit RGB
+    #[value(name = "truecolor")]
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Detects the color depth from the `COLORTERM` and `TERM` environment variables,
+    /// falling back to [ColorDepth::Basic]
+    pub fn from_env() -> ColorDepth {
+        ColorDepth::from_vars(
+            env::var("COLORTERM").ok().as_deref(),
+            env::var("TERM").ok().as_deref(),
+        )
+    }
+
+    fn from_vars(colorterm: Option<&str>, term: Option<&str>) -> ColorDepth {
+        match (colorterm, term) {
+            (Some("truecolor" | "24bit"), _) => ColorDepth::TrueColor,
+            (_, Some(term)) if term.contains("256color") => ColorDepth::Indexed,
+            _ => ColorDepth::Basic,
+        }
+    }
+
+    /// Retrieves the [Theme] for the color depth
+    pub fn theme(&self) -> &'static Theme {
+        match self {
+            ColorDepth::Basic => &BASIC,
+            ColorDepth::Indexed => &INDEXED,
+            ColorDepth::TrueColor => &TRUE_COLOR,
+        }
+    }
+}
+
+/// The colors the game is rendered with
+pub struct Theme {
+    pub selected: Color,
+    pub move_valid: Color,
+    pub move_invalid: Color,
+    pub placeholder: Color,
+    pub face_bg: Color,
+    /// The color card faces are shaded to at the bottom, if the theme has a gradient
+    pub face_shade: Option<Color>,
+    pub red_suit: Color,
+    pub black_suit: Color,
+    pub back_bg: Color,
+    pub back_fg: Color,
+}
+
+const BASIC: Theme = Theme {
+    selected: Color::LightGreen,
+    move_valid: Color::Green,
+    move_invalid: Color::Red,
+    placeholder: Color::DarkGray,
+    face_bg: Color::White,
+    face_shade: None,
+    red_suit: Color::Red,
+    black_suit: Color::DarkGray,
+    back_bg: Color::Red,
+    back_fg: Color::LightRed,
+};
+
+const INDEXED: Theme = Theme {
+    selected: Color::Indexed(120),
+    move_valid: Color::Indexed(34),
+    move_invalid: Color::Indexed(160),
+    placeholder: Color::Indexed(244),
+    face_bg: Color::Indexed(255),
+    face_shade: None,
+    red_suit: Color::Indexed(160),
+    black_suit: Color::Indexed(235),
+    back_bg: Color::Indexed(124),
+    back_fg: Color::Indexed(167),
+};
+
+const TRUE_COLOR: Theme = Theme {
+    selected: Color::Rgb(144, 238, 144),
+    move_valid: Color::Rgb(46, 160, 67),
+    move_invalid: Color::Rgb(218, 54, 51),
+    placeholder: Color::Rgb(128, 128, 128),
+    face_bg: Color::Rgb(250, 248, 240),
+    face_shade: Some(Color::Rgb(226, 221, 204)),
+    red_suit: Color::Rgb(200, 30, 45),
+    black_suit: Color::Rgb(30, 30, 30),
+    back_bg: Color::Rgb(150, 25, 35),
+    back_fg: Color::Rgb(200, 70, 80),
+};
+
+impl Theme {
+    /// The background of the given row of a card face that's `height` rows tall,
+    /// shaded from [face_bg](Theme::face_bg) at the top to [face_shade](Theme::face_shade)
+    /// at the bottom. Only RGB colors can be shaded, otherwise it's always `face_bg`
+    pub fn face_bg_row(&self, row: u16, height: u16) -> Color {
+        match (self.face_bg, self.face_shade) {
+            (Color::Rgb(r0, g0, b0), Some(Color::Rgb(r1, g1, b1))) if height > 1 => {
+                let mix = |a: u8, b: u8| {
+                    (a as i32 + (b as i32 - a as i32) * row as i32 / (height as i32 - 1)) as u8
+                };
+                Color::Rgb(mix(r0, r1), mix(g0, g1), mix(b0, b1))
+            }
+            _ => self.face_bg,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_vars() {
+        assert_eq!(
+            ColorDepth::from_vars(Some("truecolor"), Some("xterm-256color")),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            ColorDepth::from_vars(Some("24bit"), None),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            ColorDepth::from_vars(None, Some("screen-256color")),
+            ColorDepth::Indexed
+        );
+        assert_eq!(
+            ColorDepth::from_vars(None, Some("xterm")),
+            ColorDepth::Basic
+        );
+        assert_eq!(ColorDepth::from_vars(None, None), ColorDepth::Basic);
+    }
+
+    #[test]
+    fn test_face_bg_row() {
+        assert_eq!(TRUE_COLOR.face_bg_row(0, 5), Color::Rgb(250, 248, 240));
+        assert_eq!(TRUE_COLOR.face_bg_row(2, 5), Color::Rgb(238, 235, 222));
+        assert_eq!(TRUE_COLOR.face_bg_row(4, 5), Color::Rgb(226, 221, 204));
+        // A single row isn't shaded
+        assert_eq!(TRUE_COLOR.face_bg_row(0, 1), Color::Rgb(250, 248, 240));
+        // Themes without a gradient always use face_bg
+        assert_eq!(INDEXED.face_bg_row(4, 5), Color::Indexed(255));
+    }
+}
@@ -6,10 +6,12 @@ use solitaire::{
         klondike,
         klondike::{DealResult, GameStateOption},
     },
-    GameState,
+    GameState, GameStateExt,
 };
 
-#[derive(Eq, PartialEq)]
+use crate::component::game::Settings;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum Direction {
     Up,
     Down,
@@ -18,7 +20,7 @@ pub enum Direction {
 }
 
 /// Enum describing the various states the UI can be in
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum UIState {
     /// Animated dealing state at the start of a game
     /// while the cards are being dealt onto the tableau
@@ -29,32 +31,82 @@ pub enum UIState {
     Selecting(SelectingState),
     /// The user is moving their selected cards to another pile
     Moving(MovingState),
+    /// The user is typing a quick move command, a source pile then a destination pile
+    Command(CommandState),
+}
+
+/// Maps a [handle_goto](State::handle_goto) index to a pile in the top row, i.e. the
+/// [piles](GameStateExt::piles) of `game_state` that aren't in the tableau, from left to right.
+/// Indexes start from 1, so 1 is the stock
+pub fn goto_pile(game_state: &GameStateOption, i: u8) -> Option<klondike::PileRef> {
+    game_state
+        .piles()
+        .into_iter()
+        .filter(|p| !matches!(p, klondike::PileRef::Tableau(_)))
+        .nth((i as usize).checked_sub(1)?)
+}
+
+/// Maps a [handle_goto_tableau](State::handle_goto_tableau) index to a tableau pile of
+/// `game_state`. Indexes start from 1, so 1 is the leftmost pile
+pub fn goto_tableau_pile(game_state: &GameStateOption, i: u8) -> Option<klondike::PileRef> {
+    game_state
+        .piles()
+        .into_iter()
+        .filter(|p| matches!(p, klondike::PileRef::Tableau(_)))
+        .nth((i as usize).checked_sub(1)?)
 }
 
 pub trait State: Sized {
-    fn handle_tick(self, dt: &Duration, game_state: &mut GameStateOption) -> UIState;
+    fn handle_tick(
+        self,
+        dt: &Duration,
+        game_state: &mut GameStateOption,
+        settings: &Settings,
+    ) -> UIState;
 
     fn handle_direction(
         self,
         dir: Direction,
         modifier: KeyModifiers,
         game_state: &GameStateOption,
+        settings: &Settings,
     ) -> UIState;
 
-    fn handle_interact(self, game_state: &mut GameStateOption) -> UIState;
+    fn handle_interact(self, game_state: &mut GameStateOption, settings: &Settings) -> UIState;
+
+    fn handle_goto(self, i: u8, game_state: &GameStateOption) -> UIState;
 
-    fn handle_goto(self, i: u8) -> UIState;
+    fn handle_goto_tableau(self, i: u8, game_state: &GameStateOption) -> UIState;
 
     fn handle_cancel(self) -> UIState;
+
+    fn handle_select_max(self, game_state: &GameStateOption, settings: &Settings) -> UIState;
+
+    fn handle_to_foundation(self, game_state: &mut GameStateOption) -> UIState;
+
+    fn handle_command(self) -> UIState;
+
+    fn handle_command_pile(
+        self,
+        pile: klondike::PileRef,
+        game_state: &mut GameStateOption,
+        settings: &Settings,
+    ) -> UIState;
 }
 
 impl State for UIState {
-    fn handle_tick(self, dt: &Duration, game_state: &mut GameStateOption) -> UIState {
+    fn handle_tick(
+        self,
+        dt: &Duration,
+        game_state: &mut GameStateOption,
+        settings: &Settings,
+    ) -> UIState {
         match self {
-            UIState::Dealing(s) => s.handle_tick(dt, game_state),
-            UIState::Hovering(s) => s.handle_tick(dt, game_state),
-            UIState::Selecting(s) => s.handle_tick(dt, game_state),
-            UIState::Moving(s) => s.handle_tick(dt, game_state),
+            UIState::Dealing(s) => s.handle_tick(dt, game_state, settings),
+            UIState::Hovering(s) => s.handle_tick(dt, game_state, settings),
+            UIState::Selecting(s) => s.handle_tick(dt, game_state, settings),
+            UIState::Moving(s) => s.handle_tick(dt, game_state, settings),
+            UIState::Command(s) => s.handle_tick(dt, game_state, settings),
         }
     }
 
@@ -63,30 +115,44 @@ impl State for UIState {
         dir: Direction,
         modifier: KeyModifiers,
         game_state: &GameStateOption,
+        settings: &Settings,
     ) -> UIState {
         match self {
-            UIState::Dealing(s) => s.handle_direction(dir, modifier, game_state),
-            UIState::Hovering(s) => s.handle_direction(dir, modifier, game_state),
-            UIState::Selecting(s) => s.handle_direction(dir, modifier, game_state),
-            UIState::Moving(s) => s.handle_direction(dir, modifier, game_state),
+            UIState::Dealing(s) => s.handle_direction(dir, modifier, game_state, settings),
+            UIState::Hovering(s) => s.handle_direction(dir, modifier, game_state, settings),
+            UIState::Selecting(s) => s.handle_direction(dir, modifier, game_state, settings),
+            UIState::Moving(s) => s.handle_direction(dir, modifier, game_state, settings),
+            UIState::Command(s) => s.handle_direction(dir, modifier, game_state, settings),
+        }
+    }
+
+    fn handle_interact(self, game_state: &mut GameStateOption, settings: &Settings) -> UIState {
+        match self {
+            UIState::Dealing(s) => s.handle_interact(game_state, settings),
+            UIState::Hovering(s) => s.handle_interact(game_state, settings),
+            UIState::Selecting(s) => s.handle_interact(game_state, settings),
+            UIState::Moving(s) => s.handle_interact(game_state, settings),
+            UIState::Command(s) => s.handle_interact(game_state, settings),
         }
     }
 
-    fn handle_interact(self, game_state: &mut GameStateOption) -> UIState {
+    fn handle_goto(self, i: u8, game_state: &GameStateOption) -> UIState {
         match self {
-            UIState::Dealing(s) => s.handle_interact(game_state),
-            UIState::Hovering(s) => s.handle_interact(game_state),
-            UIState::Selecting(s) => s.handle_interact(game_state),
-            UIState::Moving(s) => s.handle_interact(game_state),
+            UIState::Dealing(s) => s.handle_goto(i, game_state),
+            UIState::Hovering(s) => s.handle_goto(i, game_state),
+            UIState::Selecting(s) => s.handle_goto(i, game_state),
+            UIState::Moving(s) => s.handle_goto(i, game_state),
+            UIState::Command(s) => s.handle_goto(i, game_state),
         }
     }
 
-    fn handle_goto(self, i: u8) -> UIState {
+    fn handle_goto_tableau(self, i: u8, game_state: &GameStateOption) -> UIState {
         match self {
-            UIState::Dealing(s) => s.handle_goto(i),
-            UIState::Hovering(s) => s.handle_goto(i),
-            UIState::Selecting(s) => s.handle_goto(i),
-            UIState::Moving(s) => s.handle_goto(i),
+            UIState::Dealing(s) => s.handle_goto_tableau(i, game_state),
+            UIState::Hovering(s) => s.handle_goto_tableau(i, game_state),
+            UIState::Selecting(s) => s.handle_goto_tableau(i, game_state),
+            UIState::Moving(s) => s.handle_goto_tableau(i, game_state),
+            UIState::Command(s) => s.handle_goto_tableau(i, game_state),
         }
     }
 
@@ -96,32 +162,101 @@ impl State for UIState {
             UIState::Hovering(s) => s.handle_cancel(),
             UIState::Selecting(s) => s.handle_cancel(),
             UIState::Moving(s) => s.handle_cancel(),
+            UIState::Command(s) => s.handle_cancel(),
+        }
+    }
+
+    fn handle_select_max(self, game_state: &GameStateOption, settings: &Settings) -> UIState {
+        match self {
+            UIState::Dealing(s) => s.handle_select_max(game_state, settings),
+            UIState::Hovering(s) => s.handle_select_max(game_state, settings),
+            UIState::Selecting(s) => s.handle_select_max(game_state, settings),
+            UIState::Moving(s) => s.handle_select_max(game_state, settings),
+            UIState::Command(s) => s.handle_select_max(game_state, settings),
+        }
+    }
+
+    fn handle_to_foundation(self, game_state: &mut GameStateOption) -> UIState {
+        match self {
+            UIState::Dealing(s) => s.handle_to_foundation(game_state),
+            UIState::Hovering(s) => s.handle_to_foundation(game_state),
+            UIState::Selecting(s) => s.handle_to_foundation(game_state),
+            UIState::Moving(s) => s.handle_to_foundation(game_state),
+            UIState::Command(s) => s.handle_to_foundation(game_state),
+        }
+    }
+
+    fn handle_command(self) -> UIState {
+        match self {
+            UIState::Dealing(s) => s.handle_command(),
+            UIState::Hovering(s) => s.handle_command(),
+            UIState::Selecting(s) => s.handle_command(),
+            UIState::Moving(s) => s.handle_command(),
+            UIState::Command(s) => s.handle_command(),
+        }
+    }
+
+    fn handle_command_pile(
+        self,
+        pile: klondike::PileRef,
+        game_state: &mut GameStateOption,
+        settings: &Settings,
+    ) -> UIState {
+        match self {
+            UIState::Dealing(s) => s.handle_command_pile(pile, game_state, settings),
+            UIState::Hovering(s) => s.handle_command_pile(pile, game_state, settings),
+            UIState::Selecting(s) => s.handle_command_pile(pile, game_state, settings),
+            UIState::Moving(s) => s.handle_command_pile(pile, game_state, settings),
+            UIState::Command(s) => s.handle_command_pile(pile, game_state, settings),
         }
     }
 }
 
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct DealingState {
     since_last_deal: Duration,
 }
 
 impl DealingState {
-    const DEAL_INTERVAL: Duration = Duration::from_millis(100);
-
     pub fn new() -> Self {
         DealingState {
             since_last_deal: Duration::from_secs(0),
         }
     }
+
+    /// Starts play with the dealt `new_state`, playing the opening moves if
+    /// [auto_open](Settings::auto_open) is set
+    fn finish(
+        new_state: klondike::PlayingGameState,
+        game_state: &mut GameStateOption,
+        settings: &Settings,
+    ) -> UIState {
+        let new_state = match settings.auto_open {
+            true => klondike::GameRules::play_opening(new_state),
+            false => new_state,
+        };
+        *game_state = GameStateOption::from(new_state);
+        UIState::Hovering(HoveringState::Stock)
+    }
 }
 
 impl State for DealingState {
-    fn handle_tick(self, dt: &Duration, game_state: &mut GameStateOption) -> UIState {
+    fn handle_tick(
+        self,
+        dt: &Duration,
+        game_state: &mut GameStateOption,
+        settings: &Settings,
+    ) -> UIState {
+        // Skip the animation entirely
+        if settings.reduced_motion {
+            return self.handle_interact(game_state, settings);
+        }
+
         let mut since_last_deal = self.since_last_deal + *dt;
         // Keep dealing until all the expected cards have been dealt,
         // so that slow downs don't cause fewer cards to be dealt
-        while since_last_deal >= Self::DEAL_INTERVAL {
-            since_last_deal = since_last_deal - Self::DEAL_INTERVAL;
+        while since_last_deal >= settings.deal_interval {
+            since_last_deal = since_last_deal - settings.deal_interval;
             match game_state {
                 GameStateOption::Initial(initial) => {
                     match klondike::GameRules::deal_one(initial.clone()) {
@@ -129,9 +264,8 @@ impl State for DealingState {
                             *game_state = GameStateOption::from(new_state);
                         }
                         DealResult::Complete(new_state) => {
-                            *game_state = GameStateOption::from(new_state);
                             // Deal complete, so move to hovering state
-                            return UIState::Hovering(HoveringState::Stock);
+                            return Self::finish(new_state, game_state, settings);
                         }
                     }
                 }
@@ -142,34 +276,65 @@ impl State for DealingState {
         UIState::Dealing(DealingState { since_last_deal })
     }
 
-    fn handle_direction(self, _: Direction, _: KeyModifiers, _: &GameStateOption) -> UIState {
+    fn handle_direction(
+        self,
+        _: Direction,
+        _: KeyModifiers,
+        _: &GameStateOption,
+        _: &Settings,
+    ) -> UIState {
         UIState::Dealing(self)
     }
 
-    fn handle_interact(self, game_state: &mut GameStateOption) -> UIState {
+    fn handle_interact(self, game_state: &mut GameStateOption, settings: &Settings) -> UIState {
         // Interact skips dealing
         match game_state {
             GameStateOption::Initial(initial) => {
-                *game_state = GameStateOption::from(klondike::GameRules::deal_all(initial.clone()));
+                let new_state = klondike::GameRules::deal_all(initial.clone());
+                Self::finish(new_state, game_state, settings)
             }
-            _ => {}
+            _ => UIState::Hovering(HoveringState::Stock),
         }
-        UIState::Hovering(HoveringState::Stock)
     }
 
-    fn handle_goto(self, _: u8) -> UIState {
+    fn handle_goto(self, _: u8, _: &GameStateOption) -> UIState {
+        UIState::Dealing(self)
+    }
+
+    fn handle_goto_tableau(self, _: u8, _: &GameStateOption) -> UIState {
         UIState::Dealing(self)
     }
 
     fn handle_cancel(self) -> UIState {
         UIState::Dealing(self)
     }
+
+    fn handle_select_max(self, _: &GameStateOption, _: &Settings) -> UIState {
+        UIState::Dealing(self)
+    }
+
+    fn handle_to_foundation(self, _: &mut GameStateOption) -> UIState {
+        UIState::Dealing(self)
+    }
+
+    fn handle_command(self) -> UIState {
+        UIState::Dealing(self)
+    }
+
+    fn handle_command_pile(
+        self,
+        _: klondike::PileRef,
+        _: &mut GameStateOption,
+        _: &Settings,
+    ) -> UIState {
+        UIState::Dealing(self)
+    }
 }
 
 pub type HoveringState = klondike::PileRef;
 
 impl State for HoveringState {
-    fn handle_tick(self, _: &Duration, _: &mut GameStateOption) -> UIState {
+    fn handle_tick(self, _: &Duration, _: &mut GameStateOption, _: &Settings) -> UIState {
         // no-op
         UIState::Hovering(self)
     }
@@ -179,13 +344,14 @@ impl State for HoveringState {
         dir: Direction,
         modifier: KeyModifiers,
         game_state: &GameStateOption,
+        settings: &Settings,
     ) -> UIState {
         match modifier {
             // Selection mode
             KeyModifiers::SHIFT => {
-                let pile_len = game_state.get_stack(self).unwrap().len();
+                let pile_len = game_state.get_stack(self).map_or(0, |pile| pile.len());
 
-                // Do nothing on an empty pile
+                // Do nothing on an empty or missing pile (once the game is won)
                 if pile_len == 0 {
                     return UIState::Hovering(self);
                 }
@@ -260,8 +426,8 @@ impl State for HoveringState {
                                 let pile = game_state
                                     .get_stack(klondike::PileRef::Tableau(pile_n))
                                     .unwrap();
-                                // Cannot select a face down card
-                                if !pile.get(pile.len() - 2).unwrap().face_up {
+                                // Cannot select cards that can't be moved together
+                                if klondike::movable_prefix_with(settings.seq_strategy, pile) < 2 {
                                     return UIState::Hovering(self);
                                 }
                                 UIState::Selecting(SelectingState::Tableau { pile_n, take_n: 2 })
@@ -344,14 +510,21 @@ impl State for HoveringState {
         }
     }
 
-    fn handle_interact(self, game_state: &mut GameStateOption) -> UIState {
+    fn handle_interact(self, game_state: &mut GameStateOption, settings: &Settings) -> UIState {
         match game_state {
             GameStateOption::Playing(play) => match self {
-                HoveringState::Stock => match klondike::GameRules::draw_stock(play.clone(), 1) {
-                    Ok(new_state) => *game_state = GameStateOption::Playing(new_state),
-                    Err(_) => return UIState::Hovering(self),
-                },
-                p => match klondike::GameRules::auto_move_card(play.clone(), p, 1) {
+                HoveringState::Stock => {
+                    match klondike::GameRules::draw_stock(play.clone(), settings.draw_n) {
+                        Ok(new_state) => *game_state = GameStateOption::Playing(new_state),
+                        Err(_) => return UIState::Hovering(self),
+                    }
+                }
+                p => match klondike::GameRules::auto_move_card_with(
+                    settings.seq_strategy,
+                    play.clone(),
+                    p,
+                    1,
+                ) {
                     Ok(new_state) => *game_state = GameStateOption::from(new_state),
                     Err(_) => return UIState::Hovering(self),
                 },
@@ -361,28 +534,89 @@ impl State for HoveringState {
         UIState::Hovering(self)
     }
 
-    fn handle_goto(self, i: u8) -> UIState {
-        match i {
-            1 => UIState::Hovering(HoveringState::Stock),
-            2 => UIState::Hovering(HoveringState::Talon),
-            i @ 3..=6 => UIState::Hovering(HoveringState::Foundation(i as usize - 3)),
-            _ => UIState::Hovering(self),
-        }
+    fn handle_goto(self, i: u8, game_state: &GameStateOption) -> UIState {
+        UIState::Hovering(goto_pile(game_state, i).unwrap_or(self))
+    }
+
+    fn handle_goto_tableau(self, i: u8, game_state: &GameStateOption) -> UIState {
+        UIState::Hovering(goto_tableau_pile(game_state, i).unwrap_or(self))
     }
 
     fn handle_cancel(self) -> UIState {
         // no-op
         UIState::Hovering(self)
     }
+
+    fn handle_select_max(self, game_state: &GameStateOption, settings: &Settings) -> UIState {
+        match self {
+            HoveringState::Tableau(pile_n) => {
+                // The tableau is gone once the game is won
+                match game_state
+                    .get_stack(self)
+                    .map(|pile| klondike::movable_prefix_with(settings.seq_strategy, pile))
+                {
+                    // Selecting needs at least 2 cards
+                    None | Some(0) | Some(1) => UIState::Hovering(self),
+                    Some(take_n) => UIState::Selecting(SelectingState::Tableau { pile_n, take_n }),
+                }
+            }
+            _ => UIState::Hovering(self),
+        }
+    }
+
+    fn handle_to_foundation(self, game_state: &mut GameStateOption) -> UIState {
+        match (self, &game_state) {
+            (HoveringState::Stock, _) | (HoveringState::Foundation(_), _) => {}
+            (p, GameStateOption::Playing(play)) => {
+                if let Ok(new_state) = klondike::GameRules::move_to_foundation(play.clone(), p) {
+                    *game_state = GameStateOption::from(new_state);
+                }
+            }
+            _ => {}
+        }
+        UIState::Hovering(self)
+    }
+
+    fn handle_command(self) -> UIState {
+        UIState::Command(CommandState {
+            hovering: self,
+            src: None,
+        })
+    }
+
+    fn handle_command_pile(
+        self,
+        _: klondike::PileRef,
+        _: &mut GameStateOption,
+        _: &Settings,
+    ) -> UIState {
+        UIState::Hovering(self)
+    }
 }
 
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum SelectingState {
     Tableau { pile_n: usize, take_n: usize },
 }
 
+impl SelectingState {
+    /// Starts moving the selection to `dst`, if there is one
+    fn goto(self, dst: Option<klondike::PileRef>) -> UIState {
+        match self {
+            SelectingState::Tableau { pile_n, take_n } => UIState::Moving(MovingState {
+                src: klondike::PileRef::Tableau(pile_n),
+                take_n,
+                dst: match dst {
+                    Some(dst) => dst,
+                    None => return UIState::Selecting(self),
+                },
+            }),
+        }
+    }
+}
+
 impl State for SelectingState {
-    fn handle_tick(self, _: &Duration, _: &mut GameStateOption) -> UIState {
+    fn handle_tick(self, _: &Duration, _: &mut GameStateOption, _: &Settings) -> UIState {
         // no-op
         UIState::Selecting(self)
     }
@@ -392,6 +626,7 @@ impl State for SelectingState {
         dir: Direction,
         modifier: KeyModifiers,
         game_state: &GameStateOption,
+        settings: &Settings,
     ) -> UIState {
         match self {
             SelectingState::Tableau { pile_n, take_n } => match dir {
@@ -401,12 +636,8 @@ impl State for SelectingState {
                         let pile = game_state
                             .get_stack(klondike::PileRef::Tableau(pile_n))
                             .unwrap();
-                        // No more can be selected
-                        if pile.len() == take_n {
-                            return UIState::Selecting(self);
-                        }
-                        // Cannot select a face down card
-                        if !pile.get(pile.len() - (take_n + 1)).unwrap().face_up {
+                        // No more can be moved together
+                        if take_n >= klondike::movable_prefix_with(settings.seq_strategy, pile) {
                             return UIState::Selecting(self);
                         }
                         // Otherwise increase take_n by 1
@@ -460,11 +691,12 @@ impl State for SelectingState {
         }
     }
 
-    fn handle_interact(self, game_state: &mut GameStateOption) -> UIState {
+    fn handle_interact(self, game_state: &mut GameStateOption, settings: &Settings) -> UIState {
         match game_state {
             GameStateOption::Playing(play) => match self {
                 SelectingState::Tableau { pile_n, take_n } => {
-                    match klondike::GameRules::auto_move_card(
+                    match klondike::GameRules::auto_move_card_with(
+                        settings.seq_strategy,
                         play.clone(),
                         klondike::PileRef::Tableau(pile_n),
                         take_n,
@@ -481,19 +713,12 @@ impl State for SelectingState {
         }
     }
 
-    fn handle_goto(self, i: u8) -> UIState {
-        match self {
-            SelectingState::Tableau { pile_n, take_n } => UIState::Moving(MovingState {
-                src: klondike::PileRef::Tableau(pile_n),
-                take_n,
-                dst: match i {
-                    1 => klondike::PileRef::Stock,
-                    2 => klondike::PileRef::Talon,
-                    i @ 3..=6 => klondike::PileRef::Foundation(i as usize - 3),
-                    _ => return UIState::Selecting(self),
-                },
-            }),
-        }
+    fn handle_goto(self, i: u8, game_state: &GameStateOption) -> UIState {
+        self.goto(goto_pile(game_state, i))
+    }
+
+    fn handle_goto_tableau(self, i: u8, game_state: &GameStateOption) -> UIState {
+        self.goto(goto_tableau_pile(game_state, i))
     }
 
     fn handle_cancel(self) -> UIState {
@@ -503,22 +728,77 @@ impl State for SelectingState {
             }
         }
     }
+
+    fn handle_select_max(self, game_state: &GameStateOption, settings: &Settings) -> UIState {
+        match self {
+            SelectingState::Tableau { pile_n, .. } => {
+                let pile = game_state
+                    .get_stack(klondike::PileRef::Tableau(pile_n))
+                    .unwrap();
+                match klondike::movable_prefix_with(settings.seq_strategy, pile) {
+                    // Selecting needs at least 2 cards
+                    0 | 1 => UIState::Selecting(self),
+                    take_n => UIState::Selecting(SelectingState::Tableau { pile_n, take_n }),
+                }
+            }
+        }
+    }
+
+    fn handle_to_foundation(self, _: &mut GameStateOption) -> UIState {
+        // Only a single card can be moved to a foundation
+        UIState::Selecting(self)
+    }
+
+    fn handle_command(self) -> UIState {
+        UIState::Selecting(self)
+    }
+
+    fn handle_command_pile(
+        self,
+        _: klondike::PileRef,
+        _: &mut GameStateOption,
+        _: &Settings,
+    ) -> UIState {
+        UIState::Selecting(self)
+    }
 }
 
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct MovingState {
     pub src: klondike::PileRef,
     pub take_n: usize,
     pub dst: klondike::PileRef,
 }
 
+impl MovingState {
+    /// Moves the destination to `dst`, if it's a foundation or tableau pile
+    fn goto(self, dst: Option<klondike::PileRef>) -> UIState {
+        UIState::Moving(MovingState {
+            src: self.src,
+            take_n: self.take_n,
+            dst: match dst {
+                Some(dst @ (klondike::PileRef::Foundation(_) | klondike::PileRef::Tableau(_))) => {
+                    dst
+                }
+                _ => return UIState::Moving(self),
+            },
+        })
+    }
+}
+
 impl State for MovingState {
-    fn handle_tick(self, _: &Duration, _: &mut GameStateOption) -> UIState {
+    fn handle_tick(self, _: &Duration, _: &mut GameStateOption, _: &Settings) -> UIState {
         // no-op
         UIState::Moving(self)
     }
 
-    fn handle_direction(self, dir: Direction, _: KeyModifiers, _: &GameStateOption) -> UIState {
+    fn handle_direction(
+        self,
+        dir: Direction,
+        _: KeyModifiers,
+        _: &GameStateOption,
+        _: &Settings,
+    ) -> UIState {
         let dst = match self.dst {
             // Shouldn't be possible, but handle it anyway
             klondike::PileRef::Stock => match dir {
@@ -574,10 +854,11 @@ impl State for MovingState {
         UIState::Moving(MovingState { dst, ..self })
     }
 
-    fn handle_interact(self, game_state: &mut GameStateOption) -> UIState {
+    fn handle_interact(self, game_state: &mut GameStateOption, settings: &Settings) -> UIState {
         match game_state {
             GameStateOption::Playing(play_state) => {
-                return match klondike::GameRules::move_cards(
+                return match klondike::GameRules::move_cards_with(
+                    settings.seq_strategy,
                     play_state.clone(),
                     self.src,
                     self.take_n,
@@ -595,18 +876,818 @@ impl State for MovingState {
         UIState::Hovering(self.src)
     }
 
-    fn handle_goto(self, i: u8) -> UIState {
-        UIState::Moving(MovingState {
-            src: self.src,
-            take_n: self.take_n,
-            dst: match i {
-                i @ 3..=6 => klondike::PileRef::Foundation(i as usize - 3),
-                _ => return UIState::Moving(self),
-            },
-        })
+    fn handle_goto(self, i: u8, game_state: &GameStateOption) -> UIState {
+        self.goto(goto_pile(game_state, i))
+    }
+
+    fn handle_goto_tableau(self, i: u8, game_state: &GameStateOption) -> UIState {
+        self.goto(goto_tableau_pile(game_state, i))
     }
 
     fn handle_cancel(self) -> UIState {
         UIState::Hovering(self.src)
     }
+
+    fn handle_select_max(self, _: &GameStateOption, _: &Settings) -> UIState {
+        UIState::Moving(self)
+    }
+
+    fn handle_to_foundation(self, _: &mut GameStateOption) -> UIState {
+        UIState::Moving(self)
+    }
+
+    fn handle_command(self) -> UIState {
+        UIState::Moving(self)
+    }
+
+    fn handle_command_pile(
+        self,
+        _: klondike::PileRef,
+        _: &mut GameStateOption,
+        _: &Settings,
+    ) -> UIState {
+        UIState::Moving(self)
+    }
+}
+
+/// A quick move command, e.g. `3` then `5` moves as many cards as possible
+/// from the third tableau pile onto the fifth
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct CommandState {
+    /// The pile to return to once the command is finished
+    pub hovering: HoveringState,
+    /// The pile to move from, once it's been typed
+    pub src: Option<klondike::PileRef>,
+}
+
+impl State for CommandState {
+    fn handle_tick(self, _: &Duration, _: &mut GameStateOption, _: &Settings) -> UIState {
+        // no-op
+        UIState::Command(self)
+    }
+
+    fn handle_direction(
+        self,
+        _: Direction,
+        _: KeyModifiers,
+        _: &GameStateOption,
+        _: &Settings,
+    ) -> UIState {
+        UIState::Command(self)
+    }
+
+    fn handle_interact(self, _: &mut GameStateOption, _: &Settings) -> UIState {
+        UIState::Command(self)
+    }
+
+    fn handle_goto(self, _: u8, _: &GameStateOption) -> UIState {
+        UIState::Command(self)
+    }
+
+    fn handle_goto_tableau(self, _: u8, _: &GameStateOption) -> UIState {
+        UIState::Command(self)
+    }
+
+    fn handle_cancel(self) -> UIState {
+        UIState::Hovering(self.hovering)
+    }
+
+    fn handle_select_max(self, _: &GameStateOption, _: &Settings) -> UIState {
+        UIState::Command(self)
+    }
+
+    fn handle_to_foundation(self, _: &mut GameStateOption) -> UIState {
+        UIState::Command(self)
+    }
+
+    fn handle_command(self) -> UIState {
+        // Toggles command mode back off
+        self.handle_cancel()
+    }
+
+    fn handle_command_pile(
+        self,
+        pile: klondike::PileRef,
+        game_state: &mut GameStateOption,
+        settings: &Settings,
+    ) -> UIState {
+        let src = match self.src {
+            // Cards can't be moved from the stock
+            None if pile == klondike::PileRef::Stock => return UIState::Command(self),
+            None => {
+                return UIState::Command(CommandState {
+                    src: Some(pile),
+                    ..self
+                })
+            }
+            Some(src) if src == pile => return self.handle_cancel(),
+            Some(src) => src,
+        };
+
+        let play = match game_state {
+            GameStateOption::Playing(play) => play.clone(),
+            _ => return self.handle_cancel(),
+        };
+        let result = match pile {
+            // Any foundation will do, so use whichever accepts the card
+            klondike::PileRef::Foundation(_) => {
+                klondike::GameRules::move_to_foundation(play, src).ok()
+            }
+            _ => {
+                // Only tableau piles can have more than one card taken
+                let max_n = match src {
+                    klondike::PileRef::Tableau(_) => klondike::movable_prefix_with(
+                        settings.seq_strategy,
+                        game_state.get_stack(src).unwrap(),
+                    ),
+                    _ => 1,
+                };
+                (1..=max_n).rev().find_map(|take_n| {
+                    klondike::GameRules::move_cards_with(
+                        settings.seq_strategy,
+                        play.clone(),
+                        src,
+                        take_n,
+                        pile,
+                    )
+                    .ok()
+                })
+            }
+        };
+        match result {
+            Some(result) => {
+                *game_state = GameStateOption::from(result);
+                UIState::Hovering(match pile {
+                    klondike::PileRef::Foundation(_) => src,
+                    _ => pile,
+                })
+            }
+            None => self.handle_cancel(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solitaire::variant::klondike::*;
+    use test_util::parse;
+
+    use super::*;
+
+    /// A scripted input to drive a [UIState] with
+    #[derive(Copy, Clone)]
+    enum Input {
+        Dir(Direction, KeyModifiers),
+        Interact,
+        Goto(u8),
+        GotoTableau(u8),
+        Cancel,
+        SelectMax,
+        ToFoundation,
+        Command,
+        CommandPile(PileRef),
+        Tick(Duration),
+    }
+
+    use Input::*;
+
+    const UP: Input = Dir(Direction::Up, KeyModifiers::NONE);
+    const DOWN: Input = Dir(Direction::Down, KeyModifiers::NONE);
+    const LEFT: Input = Dir(Direction::Left, KeyModifiers::NONE);
+    const RIGHT: Input = Dir(Direction::Right, KeyModifiers::NONE);
+    const SHIFT_UP: Input = Dir(Direction::Up, KeyModifiers::SHIFT);
+    const SHIFT_LEFT: Input = Dir(Direction::Left, KeyModifiers::SHIFT);
+
+    /// Feeds each of the `inputs` into the `ui_state` in turn, returning the final [UIState]
+    fn run(ui_state: UIState, game_state: &mut GameStateOption, inputs: &[Input]) -> UIState {
+        run_with(ui_state, game_state, &Settings::default(), inputs)
+    }
+
+    /// Same as [run], but with the given [Settings]
+    fn run_with(
+        ui_state: UIState,
+        game_state: &mut GameStateOption,
+        settings: &Settings,
+        inputs: &[Input],
+    ) -> UIState {
+        inputs.iter().fold(ui_state, |s, input| match *input {
+            Dir(dir, modifier) => s.handle_direction(dir, modifier, game_state, settings),
+            Interact => s.handle_interact(game_state, settings),
+            Goto(i) => s.handle_goto(i, game_state),
+            GotoTableau(i) => s.handle_goto_tableau(i, game_state),
+            Cancel => s.handle_cancel(),
+            SelectMax => s.handle_select_max(game_state, settings),
+            ToFoundation => s.handle_to_foundation(game_state),
+            Command => s.handle_command(),
+            CommandPile(p) => s.handle_command_pile(p, game_state, settings),
+            Tick(dt) => s.handle_tick(&dt, game_state, settings),
+        })
+    }
+
+    /// A mid-game fixture with a mix of face up runs, face down cards and empty piles
+    fn fixture() -> PlayingGameState {
+        PlayingGameState {
+            tableau: [
+                parse::cards(&["KS"]),
+                parse::cards(&["#2C", "QH"]),
+                parse::cards(&["#3C", "JC", "XH"]),
+                parse::cards(&["#4C", "#5C", "#6C", "9S"]),
+                Stack::new(),
+                Stack::new(),
+                parse::cards(&["AH"]),
+            ],
+            foundations: [(); NUM_FOUNDATIONS].map(|_| Stack::new()),
+            stock: parse::cards(&["#7C", "#8C"]),
+            talon: Stack::new(),
+        }
+    }
+
+    fn hovering(p: PileRef) -> UIState {
+        UIState::Hovering(p)
+    }
+
+    #[test]
+    fn test_dealing_ticks() {
+        let mut game_state = GameStateOption::from(InitialGameState::from(Card::new_deck()));
+        let start = UIState::Dealing(DealingState::new());
+
+        // Not enough time has passed to deal a card
+        let s = run(start, &mut game_state, &[Tick(Duration::from_millis(50))]);
+        assert!(matches!(s, UIState::Dealing(_)));
+        assert_eq!(game_state.get_stack(PileRef::Tableau(0)).unwrap().len(), 0);
+
+        // Two ticks that add up to a single deal
+        let s = run(s, &mut game_state, &[Tick(Duration::from_millis(50))]);
+        assert!(matches!(s, UIState::Dealing(_)));
+        assert_eq!(game_state.get_stack(PileRef::Tableau(0)).unwrap().len(), 1);
+
+        // A long tick deals everything that was missed
+        let s = run(s, &mut game_state, &[Tick(Duration::from_secs(10))]);
+        assert_eq!(s, hovering(PileRef::Stock));
+        assert!(matches!(game_state, GameStateOption::Playing(_)));
+    }
+
+    #[test]
+    fn test_dealing_reduced_motion() {
+        let mut game_state = GameStateOption::from(InitialGameState::from(Card::new_deck()));
+        let start = UIState::Dealing(DealingState::new());
+        let settings = Settings {
+            reduced_motion: true,
+            ..Settings::default()
+        };
+
+        // Everything is dealt on the first tick
+        let s = run_with(start, &mut game_state, &settings, &[Tick(Duration::ZERO)]);
+        assert_eq!(s, hovering(PileRef::Stock));
+        assert!(matches!(game_state, GameStateOption::Playing(_)));
+    }
+
+    #[test]
+    fn test_dealing_skip() {
+        let mut game_state = GameStateOption::from(InitialGameState::from(Card::new_deck()));
+        let start = UIState::Dealing(DealingState::new());
+
+        // Navigation is ignored while dealing
+        let s = run(start, &mut game_state, &[RIGHT, DOWN, Goto(3), Cancel]);
+        assert!(matches!(s, UIState::Dealing(_)));
+
+        let s = run(s, &mut game_state, &[Interact]);
+        assert_eq!(s, hovering(PileRef::Stock));
+        assert!(matches!(game_state, GameStateOption::Playing(_)));
+    }
+
+    #[test]
+    fn test_dealing_auto_open() {
+        let initial = InitialGameState::from(Card::new_deck());
+        let mut game_state = GameStateOption::from(initial.clone());
+        let settings = Settings {
+            auto_open: true,
+            ..Settings::default()
+        };
+
+        let s = run_with(
+            UIState::Dealing(DealingState::new()),
+            &mut game_state,
+            &settings,
+            &[Interact],
+        );
+        assert_eq!(s, hovering(PileRef::Stock));
+        assert_eq!(
+            game_state,
+            GameStateOption::from(GameRules::play_opening(GameRules::deal_all(initial)))
+        );
+    }
+
+    #[test]
+    fn test_hovering_navigation() {
+        let mut game_state = GameStateOption::from(fixture());
+        let start = hovering(PileRef::Stock);
+
+        // Can't leave the top left corner
+        assert_eq!(run(start, &mut game_state, &[UP, LEFT]), start);
+
+        // Along the top row
+        assert_eq!(
+            run(start, &mut game_state, &[RIGHT]),
+            hovering(PileRef::Talon)
+        );
+        assert_eq!(
+            run(start, &mut game_state, &[RIGHT, RIGHT]),
+            hovering(PileRef::Foundation(0))
+        );
+        assert_eq!(
+            run(start, &mut game_state, &[RIGHT; 10]),
+            hovering(PileRef::Foundation(3))
+        );
+        assert_eq!(
+            run(start, &mut game_state, &[RIGHT, RIGHT, LEFT, LEFT]),
+            hovering(PileRef::Stock)
+        );
+
+        // Down into the tableau, and back up again
+        assert_eq!(
+            run(start, &mut game_state, &[DOWN]),
+            hovering(PileRef::Tableau(0))
+        );
+        assert_eq!(
+            run(start, &mut game_state, &[RIGHT, DOWN]),
+            hovering(PileRef::Tableau(1))
+        );
+        assert_eq!(
+            run(start, &mut game_state, &[RIGHT, RIGHT, DOWN]),
+            hovering(PileRef::Tableau(3))
+        );
+        assert_eq!(
+            run(start, &mut game_state, &[DOWN, RIGHT, RIGHT, UP]),
+            hovering(PileRef::Talon)
+        );
+        assert_eq!(
+            run(
+                start,
+                &mut game_state,
+                &[DOWN, RIGHT, RIGHT, RIGHT, RIGHT, UP]
+            ),
+            hovering(PileRef::Foundation(1))
+        );
+
+        // Can't leave the bottom right corner
+        assert_eq!(
+            run(
+                start,
+                &mut game_state,
+                &[DOWN, DOWN, RIGHT, RIGHT, RIGHT, RIGHT, RIGHT, RIGHT, RIGHT]
+            ),
+            hovering(PileRef::Tableau(6))
+        );
+
+        // Navigation doesn't change the game
+        assert_eq!(game_state, GameStateOption::from(fixture()));
+    }
+
+    #[test]
+    fn test_hovering_goto() {
+        let mut game_state = GameStateOption::from(fixture());
+        let start = hovering(PileRef::Tableau(4));
+
+        assert_eq!(
+            run(start, &mut game_state, &[Goto(1)]),
+            hovering(PileRef::Stock)
+        );
+        assert_eq!(
+            run(start, &mut game_state, &[Goto(2)]),
+            hovering(PileRef::Talon)
+        );
+        for i in 3..=6 {
+            assert_eq!(
+                run(start, &mut game_state, &[Goto(i)]),
+                hovering(PileRef::Foundation(i as usize - 3))
+            );
+        }
+        assert_eq!(run(start, &mut game_state, &[Goto(7)]), start);
+        assert_eq!(run(start, &mut game_state, &[Cancel]), start);
+    }
+
+    #[test]
+    fn test_hovering_goto_tableau() {
+        let mut game_state = GameStateOption::from(fixture());
+        let start = hovering(PileRef::Stock);
+
+        for i in 1..=7 {
+            assert_eq!(
+                run(start, &mut game_state, &[GotoTableau(i)]),
+                hovering(PileRef::Tableau(i as usize - 1))
+            );
+        }
+        assert_eq!(run(start, &mut game_state, &[GotoTableau(0)]), start);
+        assert_eq!(run(start, &mut game_state, &[GotoTableau(8)]), start);
+    }
+
+    #[test]
+    fn test_hovering_interact() {
+        let mut game_state = GameStateOption::from(fixture());
+
+        // Interacting with the stock draws a card
+        let s = run(hovering(PileRef::Stock), &mut game_state, &[Interact]);
+        assert_eq!(s, hovering(PileRef::Stock));
+        assert_eq!(
+            game_state.get_stack(PileRef::Talon).unwrap(),
+            &parse::cards(&["8C"])
+        );
+
+        // Interacting with a tableau automatically moves the card
+        let s = run(hovering(PileRef::Tableau(6)), &mut game_state, &[Interact]);
+        assert_eq!(s, hovering(PileRef::Tableau(6)));
+        assert!(game_state
+            .get_stack(PileRef::Tableau(6))
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            game_state.get_stack(PileRef::Foundation(0)).unwrap(),
+            &parse::cards(&["AH"])
+        );
+    }
+
+    #[test]
+    fn test_hovering_interact_draw_three() {
+        let mut game_state = GameStateOption::from(PlayingGameState {
+            stock: parse::cards(&["#7C", "#8C", "#9C", "#XC"]),
+            ..fixture()
+        });
+        let settings = Settings {
+            draw_n: 3,
+            ..Settings::default()
+        };
+
+        let s = run_with(
+            hovering(PileRef::Stock),
+            &mut game_state,
+            &settings,
+            &[Interact],
+        );
+        assert_eq!(s, hovering(PileRef::Stock));
+        assert_eq!(
+            game_state.get_stack(PileRef::Stock).unwrap(),
+            &parse::cards(&["#7C"])
+        );
+        assert_eq!(
+            game_state.get_stack(PileRef::Talon).unwrap(),
+            &parse::cards(&["8C", "9C", "XC"])
+        );
+    }
+
+    #[test]
+    fn test_selecting() {
+        let mut game_state = GameStateOption::from(fixture());
+
+        // Can't select a face down card
+        assert_eq!(
+            run(hovering(PileRef::Tableau(1)), &mut game_state, &[SHIFT_UP]),
+            hovering(PileRef::Tableau(1))
+        );
+        // Can't select an empty pile
+        assert_eq!(
+            run(hovering(PileRef::Tableau(4)), &mut game_state, &[SHIFT_UP]),
+            hovering(PileRef::Tableau(4))
+        );
+
+        let s = run(hovering(PileRef::Tableau(2)), &mut game_state, &[SHIFT_UP]);
+        assert_eq!(
+            s,
+            UIState::Selecting(SelectingState::Tableau {
+                pile_n: 2,
+                take_n: 2
+            })
+        );
+        // Blocked by the face down card
+        assert_eq!(run(s, &mut game_state, &[SHIFT_UP]), s);
+        // Decreasing back to a single card goes back to hovering
+        assert_eq!(
+            run(s, &mut game_state, &[DOWN]),
+            hovering(PileRef::Tableau(2))
+        );
+        assert_eq!(
+            run(s, &mut game_state, &[Cancel]),
+            hovering(PileRef::Tableau(2))
+        );
+
+        // Selecting then moving the run onto another pile
+        let s = run(s, &mut game_state, &[LEFT]);
+        assert_eq!(
+            s,
+            UIState::Moving(MovingState {
+                src: PileRef::Tableau(2),
+                take_n: 2,
+                dst: PileRef::Tableau(1),
+            })
+        );
+        let s = run(s, &mut game_state, &[Interact]);
+        assert_eq!(s, hovering(PileRef::Tableau(1)));
+        assert_eq!(
+            game_state.get_stack(PileRef::Tableau(1)).unwrap(),
+            &parse::cards(&["#2C", "QH", "JC", "XH"])
+        );
+        assert_eq!(
+            game_state.get_stack(PileRef::Tableau(2)).unwrap(),
+            &parse::cards(&["3C"])
+        );
+    }
+
+    #[test]
+    fn test_selecting_max() {
+        let mut play = fixture();
+        play.tableau[5] = parse::cards(&["#8C", "KS", "QS", "JH", "XC"]);
+        let mut game_state = GameStateOption::from(play);
+
+        // Selects the whole valid run, but not the King of the same color below it
+        let s = run(hovering(PileRef::Tableau(5)), &mut game_state, &[SelectMax]);
+        let max = UIState::Selecting(SelectingState::Tableau {
+            pile_n: 5,
+            take_n: 3,
+        });
+        assert_eq!(s, max);
+        // Shift+Up is bounded the same way
+        assert_eq!(
+            run(
+                hovering(PileRef::Tableau(5)),
+                &mut game_state,
+                &[SHIFT_UP, SHIFT_UP, SHIFT_UP]
+            ),
+            max
+        );
+        // Selecting the max again is a no-op
+        assert_eq!(run(s, &mut game_state, &[SelectMax]), max);
+
+        // Nothing to select on a single card or a face down card
+        assert_eq!(
+            run(hovering(PileRef::Tableau(0)), &mut game_state, &[SelectMax]),
+            hovering(PileRef::Tableau(0))
+        );
+        assert_eq!(
+            run(hovering(PileRef::Tableau(1)), &mut game_state, &[SelectMax]),
+            hovering(PileRef::Tableau(1))
+        );
+        // Or outside the tableau
+        assert_eq!(
+            run(hovering(PileRef::Stock), &mut game_state, &[SelectMax]),
+            hovering(PileRef::Stock)
+        );
+
+        // Or once the game is won
+        let mut game_state = GameStateOption::Win(WinGameState {
+            foundations: Default::default(),
+        });
+        assert_eq!(
+            run(
+                hovering(PileRef::Tableau(5)),
+                &mut game_state,
+                &[SelectMax, SHIFT_UP]
+            ),
+            hovering(PileRef::Tableau(5))
+        );
+    }
+
+    #[test]
+    fn test_selecting_relaxed() {
+        let mut play = fixture();
+        play.tableau[5] = parse::cards(&["#8C", "8H", "7D", "4S"]);
+        let settings = Settings {
+            seq_strategy: SeqStrategy::Relaxed,
+            ..Settings::default()
+        };
+
+        // The run is out of sequence, so only the top card can be taken normally
+        let mut game_state = GameStateOption::from(play.clone());
+        assert_eq!(
+            run(hovering(PileRef::Tableau(5)), &mut game_state, &[SelectMax]),
+            hovering(PileRef::Tableau(5))
+        );
+
+        // But the whole face up run can be taken when relaxed,
+        // and moved onto the 9♠ as the 8♥ at its bottom fits
+        let s = run_with(
+            hovering(PileRef::Tableau(5)),
+            &mut game_state,
+            &settings,
+            &[SelectMax, LEFT, LEFT],
+        );
+        assert_eq!(
+            s,
+            UIState::Moving(MovingState {
+                src: PileRef::Tableau(5),
+                take_n: 3,
+                dst: PileRef::Tableau(3),
+            })
+        );
+        let s = run_with(s, &mut game_state, &settings, &[Interact]);
+        assert_eq!(s, hovering(PileRef::Tableau(3)));
+        assert_eq!(
+            game_state.get_stack(PileRef::Tableau(3)).unwrap(),
+            &parse::cards(&["#4C", "#5C", "#6C", "9S", "8H", "7D", "4S"])
+        );
+        assert_eq!(
+            game_state.get_stack(PileRef::Tableau(5)).unwrap(),
+            &parse::cards(&["8C"])
+        );
+
+        // Quick move commands take the whole run too
+        let mut game_state = GameStateOption::from(play);
+        let s = run_with(
+            hovering(PileRef::Stock),
+            &mut game_state,
+            &settings,
+            &[
+                Command,
+                CommandPile(PileRef::Tableau(5)),
+                CommandPile(PileRef::Tableau(3)),
+            ],
+        );
+        assert_eq!(s, hovering(PileRef::Tableau(3)));
+        assert_eq!(
+            game_state.get_stack(PileRef::Tableau(5)).unwrap(),
+            &parse::cards(&["8C"])
+        );
+    }
+
+    #[test]
+    fn test_hovering_to_foundation() {
+        let mut game_state = GameStateOption::from(fixture());
+
+        // The Ace goes to the first foundation
+        let s = run(
+            hovering(PileRef::Tableau(6)),
+            &mut game_state,
+            &[ToFoundation],
+        );
+        assert_eq!(s, hovering(PileRef::Tableau(6)));
+        assert_eq!(
+            game_state.get_stack(PileRef::Foundation(0)).unwrap(),
+            &parse::cards(&["AH"])
+        );
+        assert!(game_state
+            .get_stack(PileRef::Tableau(6))
+            .unwrap()
+            .is_empty());
+
+        // The Queen has nowhere to go on the foundations, and isn't moved to the tableau
+        let before = game_state.clone();
+        let s = run(
+            hovering(PileRef::Tableau(1)),
+            &mut game_state,
+            &[ToFoundation],
+        );
+        assert_eq!(s, hovering(PileRef::Tableau(1)));
+        assert_eq!(game_state, before);
+    }
+
+    #[test]
+    fn test_moving() {
+        let mut game_state = GameStateOption::from(fixture());
+
+        // A single card on the right of the tableau moves up to the foundations
+        let s = run(hovering(PileRef::Tableau(6)), &mut game_state, &[SHIFT_UP]);
+        assert_eq!(
+            s,
+            UIState::Moving(MovingState {
+                src: PileRef::Tableau(6),
+                take_n: 1,
+                dst: PileRef::Foundation(3),
+            })
+        );
+        // Navigating the destination along the foundations
+        let s = run(s, &mut game_state, &[LEFT, LEFT, LEFT, LEFT, UP]);
+        assert_eq!(
+            s,
+            UIState::Moving(MovingState {
+                src: PileRef::Tableau(6),
+                take_n: 1,
+                dst: PileRef::Foundation(0),
+            })
+        );
+        assert_eq!(
+            run(s, &mut game_state, &[Goto(5)]),
+            UIState::Moving(MovingState {
+                src: PileRef::Tableau(6),
+                take_n: 1,
+                dst: PileRef::Foundation(2),
+            })
+        );
+        assert_eq!(
+            run(s, &mut game_state, &[Cancel]),
+            hovering(PileRef::Tableau(6))
+        );
+        // Nothing has changed yet
+        assert_eq!(game_state, GameStateOption::from(fixture()));
+
+        let s = run(s, &mut game_state, &[Interact]);
+        assert_eq!(s, hovering(PileRef::Foundation(0)));
+        assert_eq!(
+            game_state.get_stack(PileRef::Foundation(0)).unwrap(),
+            &parse::cards(&["AH"])
+        );
+    }
+
+    #[test]
+    fn test_moving_invalid() {
+        let mut game_state = GameStateOption::from(fixture());
+
+        // 9♠ can't go onto an empty space
+        let s = run(
+            hovering(PileRef::Tableau(3)),
+            &mut game_state,
+            &[SHIFT_LEFT],
+        );
+        assert_eq!(
+            s,
+            UIState::Moving(MovingState {
+                src: PileRef::Tableau(3),
+                take_n: 1,
+                dst: PileRef::Tableau(2),
+            })
+        );
+        let s = run(s, &mut game_state, &[RIGHT, RIGHT, Interact]);
+        assert_eq!(s, hovering(PileRef::Tableau(3)));
+        assert_eq!(game_state, GameStateOption::from(fixture()));
+
+        // Can't move further than the edges of the tableau
+        let s = run(
+            hovering(PileRef::Tableau(0)),
+            &mut game_state,
+            &[SHIFT_LEFT],
+        );
+        assert_eq!(s, hovering(PileRef::Tableau(0)));
+    }
+
+    #[test]
+    fn test_command() {
+        let mut game_state = GameStateOption::from(PlayingGameState {
+            tableau: [
+                parse::cards(&["KS"]),
+                parse::cards(&["#2C", "QH"]),
+                parse::cards(&["#3C", "JC", "XH"]),
+                parse::cards(&["#4C", "QD"]),
+                Stack::new(),
+                Stack::new(),
+                parse::cards(&["AH"]),
+            ],
+            ..fixture()
+        });
+        let start = hovering(PileRef::Stock);
+
+        let s = run(
+            start,
+            &mut game_state,
+            &[Command, CommandPile(PileRef::Tableau(2))],
+        );
+        assert_eq!(
+            s,
+            UIState::Command(CommandState {
+                hovering: PileRef::Stock,
+                src: Some(PileRef::Tableau(2)),
+            })
+        );
+        assert_eq!(run(s, &mut game_state, &[Cancel]), start);
+        assert_eq!(run(s, &mut game_state, &[Command]), start);
+
+        // The whole J♣ 10♥ run moves onto the Q♦
+        let s = run(s, &mut game_state, &[CommandPile(PileRef::Tableau(3))]);
+        assert_eq!(s, hovering(PileRef::Tableau(3)));
+        assert_eq!(
+            game_state.get_stack(PileRef::Tableau(3)).unwrap(),
+            &parse::cards(&["#4C", "QD", "JC", "XH"])
+        );
+        assert_eq!(
+            game_state.get_stack(PileRef::Tableau(2)).unwrap(),
+            &parse::cards(&["3C"])
+        );
+
+        // Any foundation is the destination for the Ace
+        let s = run(
+            start,
+            &mut game_state,
+            &[
+                Command,
+                CommandPile(PileRef::Tableau(6)),
+                CommandPile(PileRef::Foundation(0)),
+            ],
+        );
+        assert_eq!(s, hovering(PileRef::Tableau(6)));
+        assert_eq!(
+            game_state.get_stack(PileRef::Foundation(0)).unwrap(),
+            &parse::cards(&["AH"])
+        );
+
+        // An invalid move leaves the game as it was
+        let before = game_state.clone();
+        let s = run(
+            start,
+            &mut game_state,
+            &[
+                Command,
+                CommandPile(PileRef::Tableau(1)),
+                CommandPile(PileRef::Tableau(4)),
+            ],
+        );
+        assert_eq!(s, start);
+        assert_eq!(game_state, before);
+    }
 }
@@ -1,6 +1,11 @@
 use std::{sync, thread, time::Instant};
 
 use crossterm::event;
+#[cfg(unix)]
+use signal_hook::{
+    consts::{SIGCONT, SIGHUP, SIGINT, SIGTERM, SIGTSTP},
+    iterator::Signals,
+};
 
 use crate::error::Error;
 
@@ -29,6 +34,12 @@ impl From<event::Event> for Event {
 pub enum Message {
     Event(Event),
     Tick(std::time::Duration),
+    /// The process was asked to terminate by a signal
+    Terminate,
+    /// The process was asked to suspend by a signal
+    Suspend,
+    /// The process was continued after being stopped
+    Resume,
 }
 
 #[derive(Copy, Clone)]
@@ -44,7 +55,7 @@ pub struct Events {
 }
 
 impl Events {
-    pub fn new(tick_rate: u64) -> Events {
+    pub fn new(tick_rate: u64) -> Result<Events, Error> {
         let (tx, rx) = sync::mpsc::channel();
 
         {
@@ -69,7 +80,28 @@ impl Events {
             });
         }
 
-        Events { rx }
+        // Forward job control and termination signals
+        // so the app can quit or suspend cleanly and restore the terminal
+        #[cfg(unix)]
+        {
+            let tx = tx.clone();
+            let mut signals = Signals::new([SIGINT, SIGTERM, SIGHUP, SIGTSTP, SIGCONT])?;
+            thread::spawn(move || {
+                for signal in signals.forever() {
+                    let message = match signal {
+                        SIGTSTP => Message::Suspend,
+                        SIGCONT => Message::Resume,
+                        _ => Message::Terminate,
+                    };
+                    match tx.send(message) {
+                        Ok(_) => {}
+                        Err(_) => break,
+                    }
+                }
+            });
+        }
+
+        Ok(Events { rx })
     }
 
     pub fn next(&self) -> Result<Message, sync::mpsc::RecvError> {
@@ -0,0 +1,140 @@
+use std::env;
+
+/// The languages the UI can be displayed in
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum Language {
+    #[default]
+    #[value(name = "en")]
+    English,
+    #[value(name = "fr")]
+    French,
+}
+
+impl Language {
+    /// Detects the language from the locale environment variables,
+    /// falling back to [Language::English] if it isn't supported
+    pub fn from_env() -> Language {
+        // Checked in order of precedence, as per POSIX
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| env::var(var).ok())
+            .find(|locale| !locale.is_empty())
+            .map_or(Language::English, |locale| Language::from_locale(&locale))
+    }
+
+    /// Parses a locale such as `fr_FR.UTF-8`
+    fn from_locale(locale: &str) -> Language {
+        match locale.split(['_', '.', '@']).next() {
+            Some("fr") => Language::French,
+            _ => Language::English,
+        }
+    }
+
+    /// Retrieves the [Strings] catalog for the language
+    pub fn strings(&self) -> &'static Strings {
+        match self {
+            Language::English => &ENGLISH,
+            Language::French => &FRENCH,
+        }
+    }
+}
+
+/// The text displayed by the UI, in a single [Language]
+pub struct Strings {
+    pub title: &'static str,
+    pub time_left: &'static str,
+    pub help_out_of_time: &'static str,
+    pub help_dealing: &'static str,
+    pub help_stock: &'static str,
+    pub help_talon: &'static str,
+    pub help_foundation: &'static str,
+    pub help_tableau: &'static str,
+    pub help_selecting: &'static str,
+    pub help_moving: &'static str,
+    pub help_command: &'static str,
+    pub count_title: &'static str,
+    pub count_legend: &'static str,
+    pub pile_stock: &'static str,
+    pub pile_talon: &'static str,
+    pub pile_foundation: &'static str,
+    pub pile_tableau: &'static str,
+    // Announcements, with `{placeholders}` substituted in
+    pub announce_moved: &'static str,
+    pub announce_moved_many: &'static str,
+    pub announce_onto: &'static str,
+    pub announce_revealed: &'static str,
+    pub announce_drew: &'static str,
+    pub announce_recycled: &'static str,
+    pub announce_won: &'static str,
+}
+
+const ENGLISH: Strings = Strings {
+    title: "Klondike",
+    time_left: "time left",
+    help_out_of_time: "out of time! | [r]estart",
+    help_dealing: "skip: ␣",
+    help_stock: "navigate: ← ↑ ↓ → | draw: ␣ | jump: 1-6, t + 1-7 | quick move: : | known cards: k | [r]estart",
+    help_talon: "navigate: ← ↑ ↓ → | move: ⇧ + ← ↑ ↓ → | [f]oundation | [r]estart",
+    help_foundation: "navigate: ← ↑ ↓ → | move: ⇧ + ← ↑ ↓ → | [r]estart",
+    help_tableau: "navigate: ← ↑ ↓ → | move: ⇧ + ← → | take more: ⇧ + ↑ | take all: m | [f]oundation | [r]estart",
+    help_selecting:
+        "take more: ⇧ + ↑ | take less: ↓ | take all: m | move: ← → | [c]ancel | [r]estart",
+    help_moving: "move: ← ↑ ↓ → | place: ␣ | [c]ancel | [r]estart",
+    help_command: "from: 1-7, 0 talon | to: 1-7, [f]oundation | [c]ancel",
+    count_title: "known cards",
+    count_legend: "f foundation  v visible  ? unseen",
+    pile_stock: "the stock",
+    pile_talon: "the talon",
+    pile_foundation: "foundation",
+    pile_tableau: "tableau",
+    announce_moved: "moved {card} from {src} to {dst}",
+    announce_moved_many: "moved {card} and {n} more from {src} to {dst}",
+    announce_onto: "{card} on {pile}",
+    announce_revealed: "revealed {card}",
+    announce_drew: "drew {card}",
+    announce_recycled: "turned the talon over",
+    announce_won: "won the game!",
+};
+
+const FRENCH: Strings = Strings {
+    title: "Klondike",
+    time_left: "temps restant",
+    help_out_of_time: "temps écoulé ! | [r]ecommencer",
+    help_dealing: "passer : ␣",
+    help_stock: "naviguer : ← ↑ ↓ → | piocher : ␣ | aller à : 1-6, t + 1-7 | déplacement rapide : : | cartes connues : k | [r]ecommencer",
+    help_talon: "naviguer : ← ↑ ↓ → | déplacer : ⇧ + ← ↑ ↓ → | [f]ondation | [r]ecommencer",
+    help_foundation: "naviguer : ← ↑ ↓ → | déplacer : ⇧ + ← ↑ ↓ → | [r]ecommencer",
+    help_tableau: "naviguer : ← ↑ ↓ → | déplacer : ⇧ + ← → | prendre plus : ⇧ + ↑ | tout prendre : m | [f]ondation | [r]ecommencer",
+    help_selecting:
+        "prendre plus : ⇧ + ↑ | prendre moins : ↓ | tout prendre : m | déplacer : ← → | [c] annuler | [r]ecommencer",
+    help_moving: "déplacer : ← ↑ ↓ → | poser : ␣ | [c] annuler | [r]ecommencer",
+    help_command: "depuis : 1-7, 0 talon | vers : 1-7, [f]ondation | [c] annuler",
+    count_title: "cartes connues",
+    count_legend: "f fondation  v visible  ? inconnue",
+    pile_stock: "la pioche",
+    pile_talon: "le talon",
+    pile_foundation: "la fondation",
+    pile_tableau: "la colonne",
+    announce_moved: "{card} déplacé de {src} vers {dst}",
+    announce_moved_many: "{card} et {n} de plus déplacés de {src} vers {dst}",
+    announce_onto: "{card} sur {pile}",
+    announce_revealed: "{card} retourné",
+    announce_drew: "{card} pioché",
+    announce_recycled: "talon retourné",
+    announce_won: "partie gagnée !",
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_locale() {
+        assert_eq!(Language::from_locale("fr"), Language::French);
+        assert_eq!(Language::from_locale("fr_CA.UTF-8"), Language::French);
+        assert_eq!(Language::from_locale("fr_FR@euro"), Language::French);
+        assert_eq!(Language::from_locale("en_GB.UTF-8"), Language::English);
+        assert_eq!(Language::from_locale("C"), Language::English);
+        assert_eq!(Language::from_locale("de_DE"), Language::English);
+    }
+}
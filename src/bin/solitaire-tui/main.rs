@@ -1,64 +1,254 @@
 mod component;
 mod error;
 mod event;
+mod i18n;
+mod terminal;
 
-use std::io;
+use std::{env, io, time::Duration};
 
 use clap::Parser;
-use crossterm::{
-    event::{KeyCode, KeyModifiers},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
-use rand::prelude::{SmallRng, *};
-use rand_seeder::Seeder;
+use crossterm::event::{KeyCode, KeyModifiers};
+use rand::prelude::*;
 use ratatui::prelude::{CrosstermBackend, Terminal};
+use solitaire::{
+    rules::{Redeal, RuleSpec},
+    seed,
+    variant::klondike,
+};
 
 use crate::{
-    component::{app::AppComponent, Component},
+    component::{
+        app::AppComponent,
+        game::{feedback, theme::ColorDepth, Settings},
+        Component,
+    },
     error::Result,
     event::*,
+    i18n::Language,
+    terminal::TerminalGuard,
 };
 
 #[derive(Parser)]
 struct Args {
     #[arg(short, long)]
     seed: Option<String>,
+
+    /// The deal number to play, as printed on exit. A random deal is picked if neither
+    /// this nor the seed is given
+    #[arg(long, conflicts_with = "seed")]
+    deal: Option<u64>,
+
+    /// The number of cards to draw from the stock at a time
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=3))]
+    draw: u8,
+
+    /// The number of times the stock can be dealt through. Unlimited if not given
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    passes: Option<u64>,
+
+    /// Only use ASCII characters, for terminals without unicode support
+    #[arg(long)]
+    ascii: bool,
+
+    /// Disable colors. Also disabled if the NO_COLOR environment variable is set
+    #[arg(long)]
+    no_color: bool,
+
+    /// The colors the terminal can display, detected from COLORTERM and TERM if not given
+    #[arg(long, value_enum)]
+    color_depth: Option<ColorDepth>,
+
+    /// Play against the clock, the game must be finished within the given time (e.g. 90s, 5m)
+    #[arg(long, value_parser = parse_duration)]
+    timed: Option<Duration>,
+
+    /// The language of the UI, detected from the locale if not given
+    #[arg(long, value_enum)]
+    lang: Option<Language>,
+
+    /// Describe each move in a status line, for players using screen readers
+    #[arg(long)]
+    announce: bool,
+
+    /// The time between each update of the UI, in milliseconds
+    #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(u64).range(1..=1000))]
+    tick_rate: u64,
+
+    /// The time between each card being dealt, in milliseconds
+    #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(u64).range(1..))]
+    deal_interval: u64,
+
+    /// Disable animations, such as dealing
+    #[arg(long)]
+    reduced_motion: bool,
+
+    /// Ring the terminal bell on invalid moves and when the game is won
+    #[arg(long)]
+    bell: bool,
+
+    /// Automatically move Aces and Twos onto the foundations once the cards are dealt
+    #[arg(long)]
+    auto_open: bool,
+
+    /// Allow any face up run to be taken from the tableau,
+    /// as long as its bottom card can be placed where it's moved to
+    #[arg(long)]
+    relaxed: bool,
+
+    /// Print a summary of the rules and exit
+    #[arg(long)]
+    rules: bool,
+
+    /// Play games in a row, dealing the next game once each one is finished,
+    /// and print a summary of the session on exit
+    #[arg(long)]
+    session: bool,
+}
+
+/// Converts a number of passes through the stock into the [Redeal] rule, e.g. 3 passes is
+/// turning the talon over twice
+fn passes_to_redeal(passes: Option<u64>) -> Redeal {
+    match passes {
+        None => Redeal::Unlimited,
+        Some(0) | Some(1) => Redeal::None,
+        Some(n) => Redeal::Limited(n as usize - 1),
+    }
+}
+
+/// Parses a duration such as `90s`, `5m` or `1h`. Plain numbers are treated as seconds
+fn parse_duration(s: &str) -> std::result::Result<Duration, String> {
+    let (n, unit) = match s.find(|c: char| !c.is_ascii_digit()) {
+        Some(i) => s.split_at(i),
+        None => (s, "s"),
+    };
+    let invalid = || format!("invalid duration `{}`", s);
+    let n: u64 = n.parse().map_err(|_| invalid())?;
+    let secs = match unit {
+        "s" => Some(n),
+        "m" => n.checked_mul(60),
+        "h" => n.checked_mul(60 * 60),
+        _ => return Err(format!("unknown duration unit `{}`", unit)),
+    };
+    secs.map(Duration::from_secs).ok_or_else(invalid)
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
 
+    let redeal = passes_to_redeal(args.passes);
+    if args.rules {
+        let rules = RuleSpec {
+            redeal,
+            ..klondike::RULES
+        };
+        println!("{}", rules);
+        return Ok(());
+    }
+
+    // Without a seed, pick a deal number so the deal can be replayed with --deal
+    let deal = args.deal.unwrap_or_else(|| thread_rng().gen());
     let rng = match args.seed.as_deref() {
-        Some(seed) => Seeder::from(seed).make_rng(),
-        None => SmallRng::from_rng(thread_rng()).unwrap(),
+        Some(seed) => seed::rng_from_str(seed),
+        None => seed::rng_from_u64(deal),
     };
 
-    enable_raw_mode()?;
-    execute!(io::stdout(), EnterAlternateScreen)?;
+    let guard = TerminalGuard::new()?;
 
     let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
     terminal.clear()?;
 
-    let mut app = AppComponent::new(&rng);
-    let events = Events::new(100);
+    let settings = Settings {
+        draw_n: args.draw as usize,
+        redeal,
+        ascii: args.ascii,
+        // See https://no-color.org
+        color: !args.no_color && env::var_os("NO_COLOR").unwrap_or_default().is_empty(),
+        color_depth: args.color_depth.unwrap_or_else(ColorDepth::from_env),
+        time_limit: args.timed,
+        language: args.lang.unwrap_or_else(Language::from_env),
+        announce: args.announce,
+        deal_interval: Duration::from_millis(args.deal_interval),
+        reduced_motion: args.reduced_motion,
+        auto_open: args.auto_open,
+        seq_strategy: match args.relaxed {
+            true => klondike::SeqStrategy::Relaxed,
+            false => klondike::SeqStrategy::Strict,
+        },
+    };
+
+    let mut app = AppComponent::new(&rng, settings);
+    if args.bell {
+        app.add_feedback_sink(Box::new(feedback::Bell));
+    }
+    if args.session {
+        app.start_session();
+    }
+    let events = Events::new(args.tick_rate)?;
 
     loop {
         terminal.draw(|f| app.render(f, f.size()))?;
 
         match events.next()? {
-            Message::Event(Event::KeyPress(KeyCode::Char('q'), _))
-            | Message::Event(Event::KeyPress(KeyCode::Char('c'), KeyModifiers::CONTROL)) => break,
+            Message::Event(Event::KeyPress(KeyCode::Char('z'), KeyModifiers::CONTROL))
+            | Message::Suspend => {
+                guard.suspend()?;
+                // Force a full redraw, the screen will have been used by something else
+                terminal.clear()?;
+            }
+            Message::Resume => terminal.clear()?,
             Message::Event(event) => {
                 app.handle_event(&event)?;
+                if app.should_quit() {
+                    break;
+                }
             }
             Message::Tick(dt) => {
                 app.handle_tick(&dt)?;
             }
+            Message::Terminate => break,
         }
     }
 
-    execute!(io::stdout(), LeaveAlternateScreen)?;
-    disable_raw_mode()?;
+    // Print after leaving the alternate screen, so it ends up in the shell's scrollback
+    drop(guard);
+    let summary = match app.session_summary() {
+        Some(session) => Some(session.to_string()),
+        None => app.summary().map(|summary| summary.to_string()),
+    };
+    if let Some(summary) = summary {
+        match &args.seed {
+            Some(seed) => println!("seed:   {}", seed),
+            None => println!("deal:   {}", deal),
+        }
+        println!("{}", summary);
+    }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("90s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("5m"), Ok(Duration::from_secs(300)));
+        assert_eq!(parse_duration("1h"), Ok(Duration::from_secs(3600)));
+        assert!(parse_duration("5d").is_err());
+        assert!(parse_duration("m").is_err());
+        // Too large to fit in a u64 number of seconds
+        assert_eq!(
+            parse_duration("999999999999999999h"),
+            Err("invalid duration `999999999999999999h`".to_string())
+        );
+    }
+
+    #[test]
+    fn test_passes_to_redeal() {
+        assert_eq!(passes_to_redeal(None), Redeal::Unlimited);
+        assert_eq!(passes_to_redeal(Some(1)), Redeal::None);
+        assert_eq!(passes_to_redeal(Some(3)), Redeal::Limited(2));
+    }
+}
@@ -0,0 +1,89 @@
+use std::{
+    io, panic,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+use crossterm::{
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+#[cfg(unix)]
+use signal_hook::{consts::SIGTSTP, low_level::emulate_default_handler};
+
+use crate::error::Result;
+
+/// Whether the terminal is set up for the TUI, so it's only restored once
+static IS_SETUP: AtomicBool = AtomicBool::new(false);
+
+/// Guard which sets up the terminal for the TUI (raw mode and the alternate screen)
+/// and restores it again when dropped, including when unwinding from a panic
+pub struct TerminalGuard {
+    /// Reinstalls the panic hook that was set before the guard was created
+    restore_hook: Option<Box<dyn FnOnce()>>,
+}
+
+impl TerminalGuard {
+    pub fn new() -> Result<TerminalGuard> {
+        // Restore the terminal before the panic message is printed,
+        // otherwise it's lost along with the alternate screen
+        let prev_hook = Arc::new(panic::take_hook());
+        let hook = Arc::clone(&prev_hook);
+        panic::set_hook(Box::new(move |info| {
+            let _ = restore();
+            hook(info);
+        }));
+        let restore_hook = Box::new(move || panic::set_hook(Box::new(move |info| prev_hook(info))));
+
+        setup()?;
+        Ok(TerminalGuard {
+            restore_hook: Some(restore_hook),
+        })
+    }
+
+    /// Restores the terminal and stops the process (like the default `SIGTSTP` action),
+    /// then sets the terminal up again once the process is continued.
+    /// No-op on platforms without job control
+    pub fn suspend(&self) -> Result<()> {
+        #[cfg(unix)]
+        {
+            restore()?;
+            emulate_default_handler(SIGTSTP)?;
+            setup()?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = restore();
+        // Go back to the previous panic hook, so later panics (e.g. while printing the summary)
+        // don't try to restore the terminal. The hook can't be changed while panicking
+        if !thread::panicking() {
+            if let Some(restore_hook) = self.restore_hook.take() {
+                restore_hook();
+            }
+        }
+    }
+}
+
+/// Enables raw mode and enters the alternate screen
+fn setup() -> io::Result<()> {
+    // Set first, so a partial setup is still restored
+    IS_SETUP.store(true, Ordering::SeqCst);
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)
+}
+
+/// Leaves the alternate screen and disables raw mode, if the terminal is set up
+fn restore() -> io::Result<()> {
+    if !IS_SETUP.swap(false, Ordering::SeqCst) {
+        return Ok(());
+    }
+    execute!(io::stdout(), LeaveAlternateScreen)?;
+    disable_raw_mode()
+}
@@ -1,7 +1,5 @@
 use std::hash::Hash;
 
-use rand::seq::SliceRandom;
-
 /// A solitaire Card. `N` is the total number of variations of Cards
 pub trait Card<const N: usize>: Copy + Clone + Eq + Ord + Hash {
     /// Create a new (unshuffled) deck of Cards
@@ -30,12 +28,35 @@ pub fn shuffle<C: Card<N>, const N: usize>(d: &mut Deck<C, N>) {
     shuffle_with_rng(d, &mut rand::thread_rng())
 }
 
-/// Shuffles the given deck mutably, using the given [rand::Rng]
+/// Shuffles the given deck mutably, using the given [rand::Rng].
+///
+/// This is a Fisher–Yates shuffle: going from the last card to the second, each card is swapped
+/// with the card at a uniformly random index at or below it. Each index is picked by rejection
+/// sampling, redrawing values that would bias the result towards lower indexes.
+/// Only [next_u64](rand::RngCore::next_u64) is used, unlike
+/// [SliceRandom::shuffle](rand::seq::SliceRandom::shuffle) whose output may change between
+/// versions of `rand`, so the same [SeedRng](crate::seed::SeedRng) always deals the same deck
 pub fn shuffle_with_rng<C: Card<N>, const N: usize, RNG: rand::Rng>(
     d: &mut Deck<C, N>,
     r: &mut RNG,
 ) {
-    d.shuffle(r)
+    for i in (1..N).rev() {
+        d.swap(i, uniform_index(r, i + 1));
+    }
+}
+
+/// Picks an index in `0..bound` by rejection sampling: draws from
+/// [next_u64](rand::RngCore::next_u64) until the value is below the largest multiple of `bound`,
+/// then takes it modulo `bound`
+fn uniform_index<RNG: rand::Rng>(r: &mut RNG, bound: usize) -> usize {
+    let bound = bound as u64;
+    let limit = u64::MAX - u64::MAX % bound;
+    loop {
+        let x = r.next_u64();
+        if x < limit {
+            return (x % bound) as usize;
+        }
+    }
 }
 
 /// Returns two slices from the given slice, as a tuple of the `(remaining, taken)`
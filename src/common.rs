@@ -6,3 +6,6 @@ pub use card::*;
 
 pub mod game_state;
 pub use game_state::*;
+
+pub mod stack;
+pub use stack::*;
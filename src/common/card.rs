@@ -13,6 +13,13 @@ pub enum Color {
     Red,
 }
 
+impl Color {
+    /// Iterates over the [FrenchSuit]s of this color
+    pub fn suits(self) -> impl Iterator<Item = FrenchSuit> {
+        FrenchSuit::iter().filter(move |s| s.color() == self)
+    }
+}
+
 /// A classic "French" [Suit](solitaire::Suit), with "Clubs", "Spades", "Hearts" and "Diamonds"
 #[derive(Copy, Clone, Eq, PartialEq, Hash, EnumCountMacro)]
 pub enum FrenchSuit {
@@ -31,6 +38,11 @@ impl FrenchSuit {
         FrenchSuit::Diamonds,
     ];
 
+    /// Iterates over every suit, in the order of [VALUES](FrenchSuit::VALUES)
+    pub fn iter() -> impl Iterator<Item = FrenchSuit> {
+        FrenchSuit::VALUES.into_iter()
+    }
+
     pub fn color(&self) -> Color {
         match self {
             FrenchSuit::Clubs => Color::Black,
@@ -52,6 +64,30 @@ impl fmt::Debug for FrenchSuit {
     }
 }
 
+impl fmt::Display for FrenchSuit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Parses either the suit's symbol (e.g. `♣`) or its initial (e.g. `C`)
+impl str::FromStr for FrenchSuit {
+    type Err = solitaire::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "♣" | "C" => Ok(FrenchSuit::Clubs),
+            "♠" | "S" => Ok(FrenchSuit::Spades),
+            "♥" | "H" => Ok(FrenchSuit::Hearts),
+            "♦" | "D" => Ok(FrenchSuit::Diamonds),
+            _ => Err(solitaire::Error::InvalidInput {
+                field: "suit",
+                reason: "unknown suit",
+            }),
+        }
+    }
+}
+
 /// The standard Ranks of cards, which is King, Queen, Jack, Ten to Two and Ace.
 /// [Ord] is defined according to this ordering,
 /// as this is how cards are ordered in a [Stack](solitaire::Stack)
@@ -108,6 +144,39 @@ impl Rank {
             .and_then(|i| i.checked_sub(1))
             .and_then(|i| Rank::VALUES.get(i))
     }
+
+    /// The numeric value of the rank, from 1 for Ace to 13 for King
+    pub fn value(&self) -> u8 {
+        (Rank::N - *self as usize) as u8
+    }
+
+    /// Retrieves the rank one higher in value (e.g. Two for Ace), or [None] for King
+    pub fn succ(&self) -> Option<Rank> {
+        self.prev().cloned()
+    }
+
+    /// Retrieves the rank one lower in value (e.g. Ace for Two), or [None] for Ace
+    pub fn pred(&self) -> Option<Rank> {
+        self.next().cloned()
+    }
+
+    /// The difference in [value](Rank::value) between two ranks
+    pub fn distance(&self, other: &Rank) -> u8 {
+        self.value().abs_diff(other.value())
+    }
+
+    /// Retrieves the rank `n` higher in value, wrapping from King back round to Ace,
+    /// as in Calculation
+    pub fn add_wrapping(&self, n: u8) -> Rank {
+        let value = (self.value() as usize - 1 + n as usize) % Rank::N + 1;
+        Rank::VALUES[Rank::N - value]
+    }
+
+    /// Whether two ranks are one apart in value, where King and Ace are also adjacent,
+    /// as in Golf or Tri Peaks
+    pub fn adjacent_wrapping(&self, other: &Rank) -> bool {
+        matches!(self.distance(other), 1 | 12)
+    }
 }
 
 impl fmt::Debug for Rank {
@@ -130,6 +199,44 @@ impl fmt::Debug for Rank {
     }
 }
 
+/// Same as [Debug](fmt::Debug), except Ten is `10` rather than `X`
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rank::Ten => write!(f, "10"),
+            r => fmt::Debug::fmt(r, f),
+        }
+    }
+}
+
+/// Parses the rank's [Display](fmt::Display) or [Debug](fmt::Debug) representation.
+/// Ace can also be `1`
+impl str::FromStr for Rank {
+    type Err = solitaire::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "A" | "1" => Ok(Rank::Ace),
+            "2" => Ok(Rank::Two),
+            "3" => Ok(Rank::Three),
+            "4" => Ok(Rank::Four),
+            "5" => Ok(Rank::Five),
+            "6" => Ok(Rank::Six),
+            "7" => Ok(Rank::Seven),
+            "8" => Ok(Rank::Eight),
+            "9" => Ok(Rank::Nine),
+            "X" | "10" => Ok(Rank::Ten),
+            "J" => Ok(Rank::Jack),
+            "Q" => Ok(Rank::Queen),
+            "K" => Ok(Rank::King),
+            _ => Err(solitaire::Error::InvalidInput {
+                field: "rank",
+                reason: "unknown rank",
+            }),
+        }
+    }
+}
+
 /// A standard [Card](solitaire::Card) with a suit and a rank, and if the card is face up.
 /// [Ord] is implemented but only acts on the card's [Rank]
 #[derive(Copy, Clone, Eq, PartialEq, Hash)]
@@ -141,6 +248,12 @@ pub struct Card {
 
 impl Card {
     pub const N: usize = FrenchSuit::N * Rank::N;
+
+    /// Whether the two cards' suits are of different [Color]s
+    pub fn is_opposite_color(&self, other: &Card) -> bool {
+        self.suit.color() != other.suit.color()
+    }
+
     fn from_index(i: usize) -> Card {
         Card {
             suit: FrenchSuit::VALUES[i / Rank::N],
@@ -168,6 +281,43 @@ impl fmt::Debug for Card {
     }
 }
 
+/// The card's [Rank] followed by its [FrenchSuit], prefixed with `#` if face down, e.g. `#10♠`
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.face_up {
+            write!(f, "#")?;
+        }
+        write!(f, "{}{}", self.rank, self.suit)
+    }
+}
+
+/// Parses a card in the same format as [Display](fmt::Display),
+/// where the rank and suit can be in any format accepted by [Rank] and [FrenchSuit]
+impl str::FromStr for Card {
+    type Err = solitaire::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (face_up, s) = match s.strip_prefix('#') {
+            Some(s) => (false, s),
+            None => (true, s),
+        };
+        let suit_start = s
+            .char_indices()
+            .last()
+            .ok_or(solitaire::Error::InvalidInput {
+                field: "card",
+                reason: "empty string",
+            })?
+            .0;
+        let (rank, suit) = s.split_at(suit_start);
+        Ok(Card {
+            suit: suit.parse()?,
+            rank: rank.parse()?,
+            face_up,
+        })
+    }
+}
+
 impl solitaire::Card<{ Card::N }> for Card {
     fn new_deck() -> Deck {
         let mut i = 0;
@@ -180,3 +330,19 @@ pub type Deck = solitaire::Deck<Card, { Card::N }>;
 
 /// Convenience type alias for a [Stack](solitaire::Stack) of [Card]
 pub type Stack = solitaire::Stack<Card>;
+
+/// Builds a runtime-sized deck with only the given suits, repeated `copies` times,
+/// e.g. a two-suit Spider deck is `build_deck(&[Spades, Hearts], 4)`.
+/// Cards are face down and ordered the same as [new_deck](solitaire::Card::new_deck)
+pub fn build_deck(suits: &[FrenchSuit], copies: usize) -> Stack {
+    (0..copies)
+        .flat_map(|_| suits.iter())
+        .flat_map(|&suit| {
+            Rank::VALUES.into_iter().map(move |rank| Card {
+                suit,
+                rank,
+                face_up: false,
+            })
+        })
+        .collect()
+}
@@ -1,8 +1,10 @@
+use std::iter;
+
 use crate as solitaire;
-use crate::{shuffle, shuffle_with_rng, Card, Deck, GameState, Stack, StackFrom};
+use crate::{shuffle, shuffle_with_rng, Card, Deck, GameState, GameStateExt, Stack, StackFrom};
 
 /// "Standard" solitaire piles
-#[derive(Eq, PartialEq, Copy, Clone, Hash)]
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
 pub enum PileRef {
     /// The "tableau" of [Stack]s where cards are moved around
     Tableau(usize),
@@ -24,7 +26,7 @@ impl solitaire::PileRef for PileRef {}
 
 /// Struct for the initial [GameState] with just the [Stock](PileRef::Stock)
 /// and a (partially) dealt [Tableau](PileRef::Tableau)
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct InitialGameState<C: Card<NC>, const NC: usize, const NT: usize> {
     /// The tableau, see [Tableau](PileRef::Tableau)
     pub tableau: [Stack<C>; NT],
@@ -52,6 +54,16 @@ impl<C: Card<NC>, const NC: usize, const NT: usize> GameState<C, NC, PileRef>
     }
 }
 
+impl<C: Card<NC>, const NC: usize, const NT: usize> GameStateExt<C, NC, PileRef>
+    for InitialGameState<C, NC, NT>
+{
+    fn piles(&self) -> Vec<PileRef> {
+        iter::once(PileRef::Stock)
+            .chain((0..NT).map(PileRef::Tableau))
+            .collect()
+    }
+}
+
 impl<C: Card<NC>, const NC: usize, const NT: usize> InitialGameState<C, NC, NT> {
     pub fn new() -> InitialGameState<C, NC, NT> {
         let mut d = C::new_deck();
@@ -78,7 +90,7 @@ impl<C: Card<NC>, const NC: usize, const NT: usize> From<Deck<C, NC>>
 }
 
 /// Struct for a mid-game "playing" [GameState] with four [piles](PileRef) of generic [Card]s
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct PlayingGameState<C: Card<NC>, const NC: usize, const NT: usize, const NF: usize> {
     /// The tableau, see [Tableau](PileRef::Tableau)
     pub tableau: [Stack<C>; NT],
@@ -115,8 +127,51 @@ impl<C: Card<NC>, const NC: usize, const NT: usize, const NF: usize> GameState<C
     }
 }
 
+impl<C: Card<NC>, const NC: usize, const NT: usize, const NF: usize> GameStateExt<C, NC, PileRef>
+    for PlayingGameState<C, NC, NT, NF>
+{
+    fn piles(&self) -> Vec<PileRef> {
+        [PileRef::Stock, PileRef::Talon]
+            .into_iter()
+            .chain((0..NF).map(PileRef::Foundation))
+            .chain((0..NT).map(PileRef::Tableau))
+            .collect()
+    }
+}
+
+impl<C: Card<NC>, const NC: usize, const NT: usize, const NF: usize>
+    PlayingGameState<C, NC, NT, NF>
+{
+    /// The number of cards on all the [Foundation](PileRef::Foundation) piles combined
+    pub fn foundation_count(&self) -> usize {
+        self.foundations.iter().map(Stack::len).sum()
+    }
+
+    /// The number of cards left in the [Stock](PileRef::Stock)
+    pub fn stock_len(&self) -> usize {
+        self.stock.len()
+    }
+
+    /// Whether the given pile is empty. Piles that don't exist are considered empty
+    pub fn is_empty(&self, p: PileRef) -> bool {
+        match self.get_stack(p) {
+            Some(s) => s.is_empty(),
+            None => true,
+        }
+    }
+}
+
+impl<const NT: usize, const NF: usize>
+    PlayingGameState<crate::common::Card, { crate::common::Card::N }, NT, NF>
+{
+    /// The number of face down cards on the [Tableau](PileRef::Tableau)
+    pub fn face_down_count(&self) -> usize {
+        self.tableau.iter().flatten().filter(|c| !c.face_up).count()
+    }
+}
+
 /// Struct for a win [GameState] with just the [Foundation](PileRef::Foundation) piles
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct WinGameState<C: Card<NC>, const NC: usize, const NF: usize> {
     /// The foundations, see [Foundation](PileRef::Foundation)
     pub foundations: [Stack<C>; NF],
@@ -140,8 +195,16 @@ impl<'d, C: Card<NC>, const NC: usize, const NF: usize> GameState<C, NC, PileRef
     }
 }
 
+impl<C: Card<NC>, const NC: usize, const NF: usize> GameStateExt<C, NC, PileRef>
+    for WinGameState<C, NC, NF>
+{
+    fn piles(&self) -> Vec<PileRef> {
+        (0..NF).map(PileRef::Foundation).collect()
+    }
+}
+
 /// Enum for all possible [GameState]s
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum GameStateOption<C: Card<NC>, const NC: usize, const NT: usize, const NF: usize> {
     Initial(InitialGameState<C, NC, NT>),
     Playing(PlayingGameState<C, NC, NT, NF>),
@@ -190,6 +253,18 @@ impl<C: Card<NC>, const NC: usize, const NT: usize, const NF: usize> GameState<C
     }
 }
 
+impl<C: Card<NC>, const NC: usize, const NT: usize, const NF: usize> GameStateExt<C, NC, PileRef>
+    for GameStateOption<C, NC, NT, NF>
+{
+    fn piles(&self) -> Vec<PileRef> {
+        match self {
+            GameStateOption::Initial(s) => s.piles(),
+            GameStateOption::Playing(s) => s.piles(),
+            GameStateOption::Win(s) => s.piles(),
+        }
+    }
+}
+
 impl<C: Card<NC>, const NC: usize, const NT: usize, const NF: usize>
     From<InitialGameState<C, NC, NT>> for GameStateOption<C, NC, NT, NF>
 {
@@ -0,0 +1,118 @@
+use std::ops::Deref;
+
+use crate::{
+    common::{Card, Rank, Stack},
+    Error, Result,
+};
+
+/// Trait extension for common operations on a [Stack] of [Card]s,
+/// so rules don't need to re-derive the slice indexing.
+/// The "top" of a [Stack] is its last element
+pub trait StackExt {
+    /// Retrieves the top card, if there is one
+    fn top(&self) -> Option<&Card>;
+
+    /// Removes the top `n` cards and returns them, in the same order.
+    /// Returns [None] (and leaves the stack as is) if there are fewer than `n` cards
+    fn take_top_n(&mut self, n: usize) -> Option<Stack>;
+
+    /// Turns the top card face up, returning whether it was previously face down
+    fn flip_top(&mut self) -> bool;
+
+    /// Splits the stack into the cards below the face up run at the top,
+    /// and the face up run itself
+    fn split_face_up(&self) -> (&[Card], &[Card]);
+
+    /// Whether each card is one rank lower than the card below it, and of the opposite color
+    fn is_descending_alt_color(&self) -> bool;
+}
+
+impl StackExt for Stack {
+    fn top(&self) -> Option<&Card> {
+        self.last()
+    }
+
+    fn take_top_n(&mut self, n: usize) -> Option<Stack> {
+        let at = self.len().checked_sub(n)?;
+        Some(self.split_off(at))
+    }
+
+    fn flip_top(&mut self) -> bool {
+        match self.last_mut() {
+            Some(c) if !c.face_up => {
+                c.face_up = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn split_face_up(&self) -> (&[Card], &[Card]) {
+        let at = self.iter().rposition(|c| !c.face_up).map_or(0, |i| i + 1);
+        self.split_at(at)
+    }
+
+    fn is_descending_alt_color(&self) -> bool {
+        self.windows(2)
+            .all(|w| w[0].is_opposite_color(&w[1]) && w[0].rank.next() == Some(&w[1].rank))
+    }
+}
+
+/// A [Stack] that is guaranteed to be built up in suit from Ace, as a foundation is.
+/// Cards can only be added with [push](FoundationStack::push), which checks the ordering,
+/// so a foundation can't be corrupted without it being caught where the card is added
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FoundationStack(Stack);
+
+impl FoundationStack {
+    pub fn new() -> FoundationStack {
+        FoundationStack(Stack::new())
+    }
+
+    /// Adds `card` to the top, if it's an Ace on an empty stack
+    /// or the next rank up of the same suit as the top card
+    pub fn push(&mut self, card: Card) -> Result<()> {
+        let valid = match self.0.top() {
+            None => card.rank == Rank::Ace,
+            Some(top) => top.suit == card.suit && top.rank.succ() == Some(card.rank),
+        };
+        if !valid {
+            return Err(Error::InvalidMove {
+                reason: "foundation must be built up in suit from Ace",
+            });
+        }
+        self.0.push(card);
+        Ok(())
+    }
+
+    /// Whether every rank of the suit is on the foundation
+    pub fn is_complete(&self) -> bool {
+        self.0.len() == Rank::N
+    }
+
+    /// Unwraps the underlying [Stack]
+    pub fn into_inner(self) -> Stack {
+        self.0
+    }
+}
+
+impl Deref for FoundationStack {
+    type Target = Stack;
+
+    fn deref(&self) -> &Stack {
+        &self.0
+    }
+}
+
+/// Checks that each card of the [Stack] could have been [pushed](FoundationStack::push) in turn
+impl TryFrom<Stack> for FoundationStack {
+    type Error = Error;
+
+    fn try_from(stack: Stack) -> Result<FoundationStack> {
+        let mut foundation = FoundationStack::new();
+        for card in stack {
+            foundation.push(card)?;
+        }
+        Ok(foundation)
+    }
+}
@@ -1,7 +1,5 @@
 use std::{self, hash::Hash};
 
-use thiserror;
-
 use crate::*;
 
 /// A reference to a "Pile" of [Card]s, e.g. the stock, a foundation
@@ -16,6 +14,29 @@ pub trait GameState<C: Card<N>, const N: usize, P: PileRef>: Sized + Clone + Eq
     fn get_stack_mut(&mut self, p: P) -> Option<&mut Stack<C>>;
 }
 
+/// Trait extension for a [GameState] that can list its piles,
+/// so callers don't need to know the layout of every state
+pub trait GameStateExt<C: Card<N>, const N: usize, P: PileRef + Copy>: GameState<C, N, P> {
+    /// The [PileRef]s of every pile in the state, in layout order
+    fn piles(&self) -> Vec<P>;
+
+    /// Iterates over every [Card] in the state, in the order of [piles](GameStateExt::piles),
+    /// along with the [PileRef] of its pile and its index in the pile (0 is the bottom)
+    fn cards<'a>(&'a self) -> Box<dyn Iterator<Item = (P, usize, &'a C)> + 'a>
+    where
+        C: 'a,
+        P: 'a,
+    {
+        Box::new(self.piles().into_iter().flat_map(move |p| {
+            self.get_stack(p)
+                .into_iter()
+                .flatten()
+                .enumerate()
+                .map(move |(i, c)| (p, i, c))
+        }))
+    }
+}
+
 /// Enum of all the possible errors that may occur while operating on a [GameState]
 #[derive(Debug, thiserror::Error, Eq, PartialEq)]
 pub enum Error {
@@ -1,8 +1,14 @@
 pub mod card;
-pub use card::*;
+pub use card::{
+    shuffle, shuffle_with_rng, take_n_slice, take_n_vec_mut, take_one_slice, take_one_vec_mut,
+    Card, Deck, Stack, StackFrom,
+};
 
 pub mod game_state;
-pub use game_state::*;
+pub use game_state::{Error, GameState, GameStateExt, PileRef, Result};
 
 pub mod common;
+pub mod prelude;
+pub mod rules;
+pub mod seed;
 pub mod variant;
@@ -0,0 +1,16 @@
+//! The supported types for playing a game, with a stable import path.
+//! Prefer `use solitaire::prelude::*` over importing from the modules directly,
+//! as they may be reorganised
+
+pub use crate::{
+    common::StackExt,
+    rules::RuleSpec,
+    variant::{
+        klondike,
+        klondike::{
+            Card, DealResult, FrenchSuit, GameRules, GameStateOption, InitialGameState, MoveResult,
+            PileRef, PlayingGameState, Rank, SeqStrategy, WinGameState,
+        },
+    },
+    Card as CardTrait, Error, GameState, GameStateExt, Result, Stack, StackFrom,
+};
@@ -0,0 +1,142 @@
+//! Machine readable descriptions of the rules of each variant
+
+use std::fmt;
+
+use crate::common::Rank;
+
+/// The rules of a variant, as data rather than code, e.g. to summarise them for players
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RuleSpec {
+    /// The name of the variant
+    pub name: &'static str,
+    /// The kinds of pile in the variant, in layout order
+    pub piles: &'static [PileSpec],
+    /// How many times the stock can be dealt through
+    pub redeal: Redeal,
+}
+
+/// A kind of pile, how many of them there are and how they are built on
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PileSpec {
+    pub kind: PileKind,
+    pub count: usize,
+    pub build: Build,
+    pub matching: Matching,
+    /// What an empty pile accepts
+    pub empty: Empty,
+}
+
+/// The kinds of pile, see [PileRef](crate::common::PileRef)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PileKind {
+    Tableau,
+    Foundation,
+    Stock,
+    Talon,
+    Reserve,
+}
+
+/// The rank order cards are built on a pile in
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Build {
+    /// Cards can't be placed on the pile by the player
+    None,
+    /// Each card is one rank lower than the card it's placed on
+    Down,
+    /// Each card is one rank higher than the card it's placed on
+    Up,
+    UpOrDown,
+    Any,
+    /// Each card is as many ranks higher as the pile's position (1 for the first pile,
+    /// 2 for the second, etc.), wrapping from King to Ace
+    UpByPosition,
+    /// Cards of any rank already on the previous pile, as in Osmosis
+    RankInPrevious,
+}
+
+/// What a card must have in common with the card it's placed on
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Matching {
+    Any,
+    Suit,
+    AlternateColor,
+    /// Either the same suit or the same rank
+    SuitOrRank,
+}
+
+/// What can be placed on an empty pile
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Empty {
+    Nothing,
+    Any,
+    Rank(Rank),
+    /// The rank of the first card dealt to the foundations
+    BaseRank,
+}
+
+/// How many times the stock can be dealt through
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Redeal {
+    /// The stock is dealt through once
+    None,
+    Unlimited,
+    /// The talon can be turned over to be dealt through again this many times
+    Limited(usize),
+}
+
+impl fmt::Display for RuleSpec {
+    /// Summarises the rules, with a line per kind of pile.
+    /// The redeal rule is only given if there's a stock
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        for pile in self.piles {
+            write!(f, "\n- {}", pile)?;
+        }
+        if !self.piles.iter().any(|p| p.kind == PileKind::Stock) {
+            return Ok(());
+        }
+        match self.redeal {
+            Redeal::None => write!(f, "\nthe stock is dealt through once"),
+            Redeal::Unlimited => write!(f, "\nthe talon can be turned over any number of times"),
+            Redeal::Limited(1) => write!(f, "\nthe talon can be turned over once"),
+            Redeal::Limited(n) => write!(f, "\nthe talon can be turned over {} times", n),
+        }
+    }
+}
+
+impl fmt::Display for PileSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kind = match self.kind {
+            PileKind::Tableau => "tableau",
+            PileKind::Foundation => "foundation",
+            PileKind::Stock => "stock",
+            PileKind::Talon => "talon",
+            PileKind::Reserve => "reserve",
+        };
+        write!(f, "{} ({}): ", kind, self.count)?;
+
+        let build = match self.build {
+            Build::None => return write!(f, "not built on"),
+            Build::Down => "builds down",
+            Build::Up => "builds up",
+            Build::UpOrDown => "builds up or down",
+            Build::Any => "builds in any order",
+            Build::UpByPosition => "builds up by 1, 2, 3, etc. for each pile, wrapping",
+            Build::RankInPrevious => "builds with ranks already on the previous pile",
+        };
+        let matching = match self.matching {
+            Matching::Any => "",
+            Matching::Suit => " in suit",
+            Matching::AlternateColor => " in alternating colors",
+            Matching::SuitOrRank => " matching suit or rank",
+        };
+        write!(f, "{}{}", build, matching)?;
+
+        match self.empty {
+            Empty::Nothing => write!(f, ", empty piles stay empty"),
+            Empty::Any => write!(f, ", empty piles take any card"),
+            Empty::Rank(rank) => write!(f, ", empty piles take {}", rank),
+            Empty::BaseRank => write!(f, ", empty piles take the base card's rank"),
+        }
+    }
+}
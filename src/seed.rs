@@ -0,0 +1,22 @@
+//! Reproducible deals. The same seed gives the same deal on every platform and release,
+//! as the PRNG and the [shuffle](crate::shuffle_with_rng) are both pinned
+
+use rand::SeedableRng;
+use rand_seeder::Seeder;
+
+/// The PRNG used for seeded deals, xoshiro256++.
+/// Unlike [SmallRng](rand::rngs::SmallRng), the algorithm is the same on every platform
+/// and won't change between versions of `rand`
+pub type SeedRng = rand_xoshiro::Xoshiro256PlusPlus;
+
+/// Creates a [SeedRng] from a number, e.g. a deal number.
+/// The state is filled from `seed` with SplitMix64, as recommended for xoshiro
+pub fn rng_from_u64(seed: u64) -> SeedRng {
+    SeedRng::seed_from_u64(seed)
+}
+
+/// Creates a [SeedRng] from a human-readable seed string, such as one given on the command line.
+/// The string is hashed into the PRNG's state with [Seeder]'s SipHash-2-4
+pub fn rng_from_str(seed: &str) -> SeedRng {
+    Seeder::from(seed).make_rng()
+}
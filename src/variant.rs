@@ -1,3 +1,8 @@
 //! Contains game states and rules for Solitaire variants
 
+pub mod accordion;
+pub mod calculation;
+pub mod grandfathers_clock;
 pub mod klondike;
+pub mod osmosis;
+pub mod yukon_family;
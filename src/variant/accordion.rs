@@ -0,0 +1,186 @@
+use std::fmt;
+
+pub use common::{Card, Deck, FrenchSuit, PileRef, Rank, Stack, StackExt};
+
+use crate::{
+    common,
+    rules::{Build, Empty, Matching, PileKind, PileSpec, Redeal, RuleSpec},
+    shuffle_with_rng, GameState, GameStateExt,
+};
+pub use crate::{Card as CardTrait, Error, Result, StackFrom};
+
+/// The [RuleSpec] for Accordion, see [GameRules] for their implementation
+pub const RULES: RuleSpec = RuleSpec {
+    name: "Accordion",
+    piles: &[PileSpec {
+        kind: PileKind::Tableau,
+        count: Card::N,
+        build: Build::Any,
+        matching: Matching::SuitOrRank,
+        empty: Empty::Nothing,
+    }],
+    redeal: Redeal::None,
+};
+
+/// The mid-game "playing" [GameState] for Accordion: a single row of piles, from left to right.
+/// Piles are [Tableau](PileRef::Tableau) piles, and the row closes up as piles are moved
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlayingGameState {
+    pub piles: Vec<Stack>,
+}
+
+impl GameState<Card, { Card::N }, PileRef> for PlayingGameState {
+    fn get_stack(&self, p: PileRef) -> Option<&Stack> {
+        match p {
+            PileRef::Tableau(n) => self.piles.get(n),
+            _ => None,
+        }
+    }
+
+    fn get_stack_mut(&mut self, p: PileRef) -> Option<&mut Stack> {
+        match p {
+            PileRef::Tableau(n) => self.piles.get_mut(n),
+            _ => None,
+        }
+    }
+}
+
+impl GameStateExt<Card, { Card::N }, PileRef> for PlayingGameState {
+    fn piles(&self) -> Vec<PileRef> {
+        (0..self.piles.len()).map(PileRef::Tableau).collect()
+    }
+}
+
+/// The win [GameState] for Accordion, with every card in a single pile
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WinGameState {
+    pub pile: Stack,
+}
+
+/// Enum for the resulting [GameState] after making a move
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MoveResult {
+    Playing(PlayingGameState),
+    Win(WinGameState),
+}
+
+/// How far to the left a pile can be moved
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Distance {
+    /// Onto the pile immediately to the left
+    One,
+    /// Onto the pile three places to the left
+    Three,
+}
+
+impl fmt::Display for Distance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Distance::One => write!(f, "1"),
+            Distance::Three => write!(f, "3"),
+        }
+    }
+}
+
+impl From<Distance> for usize {
+    fn from(d: Distance) -> Self {
+        match d {
+            Distance::One => 1,
+            Distance::Three => 3,
+        }
+    }
+}
+
+/// The Game rules for Accordion
+pub struct GameRules;
+
+impl GameRules {
+    /// Lays out each card of the deck face up as its own pile, in deck order
+    pub fn deal(deck: Deck) -> PlayingGameState {
+        PlayingGameState {
+            piles: deck
+                .iter()
+                .map(|c| {
+                    vec![Card {
+                        face_up: true,
+                        ..*c
+                    }]
+                })
+                .collect(),
+        }
+    }
+
+    /// Shuffles a new deck with the given [rand::Rng] and then [deal](Self::deal)s it
+    pub fn new_and_deal_with_rng<RNG: rand::Rng>(rng: &mut RNG) -> PlayingGameState {
+        let mut deck = Card::new_deck();
+        shuffle_with_rng(&mut deck, rng);
+        Self::deal(deck)
+    }
+
+    /// Whether the top cards of the two piles match, by [FrenchSuit] or [Rank]
+    pub fn matches(src: &Card, dst: &Card) -> bool {
+        src.suit == dst.suit || src.rank == dst.rank
+    }
+
+    /// Attempts to move the pile at index `src` onto the pile `distance` places to its left,
+    /// returning a copy of `state` with the result of the move.
+    /// The top cards of both piles must [match](Self::matches)
+    pub fn move_pile(
+        state: PlayingGameState,
+        src: usize,
+        distance: Distance,
+    ) -> Result<MoveResult> {
+        if src >= state.piles.len() {
+            return Err(Error::InvalidInput {
+                field: "src",
+                reason: "pile doesn't exist",
+            });
+        }
+        let dst = match src.checked_sub(distance.into()) {
+            Some(dst) => dst,
+            None => {
+                return Err(Error::InvalidInput {
+                    field: "distance",
+                    reason: "not enough piles to the left",
+                })
+            }
+        };
+
+        match (state.piles[src].top(), state.piles[dst].top()) {
+            (Some(s), Some(d)) if Self::matches(s, d) => {}
+            _ => {
+                return Err(Error::InvalidMove {
+                    reason: "top cards must match in suit or rank",
+                })
+            }
+        }
+
+        let mut new_state = state;
+        let moved = new_state.piles.remove(src);
+        new_state.piles[dst].extend(moved);
+
+        match new_state.piles.len() {
+            1 => Ok(MoveResult::Win(WinGameState {
+                pile: new_state.piles.remove(0),
+            })),
+            _ => Ok(MoveResult::Playing(new_state)),
+        }
+    }
+
+    /// Every valid move in `state`, as the `src` pile and [Distance] for [move_pile](Self::move_pile)
+    pub fn valid_moves(state: &PlayingGameState) -> Vec<(usize, Distance)> {
+        (0..state.piles.len())
+            .flat_map(|src| [(src, Distance::One), (src, Distance::Three)])
+            .filter(|&(src, distance)| {
+                let dst = match src.checked_sub(distance.into()) {
+                    Some(dst) => dst,
+                    None => return false,
+                };
+                match (state.piles[src].top(), state.piles[dst].top()) {
+                    (Some(s), Some(d)) => Self::matches(s, d),
+                    _ => false,
+                }
+            })
+            .collect()
+    }
+}
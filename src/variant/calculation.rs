@@ -0,0 +1,219 @@
+pub use common::{Card, Deck, FrenchSuit, PileRef, Rank, Stack, StackExt};
+
+use crate::{
+    common,
+    rules::{Build, Empty, Matching, PileKind, PileSpec, Redeal, RuleSpec},
+    shuffle_with_rng, GameState, GameStateExt,
+};
+pub use crate::{Card as CardTrait, Error, Result, StackFrom};
+
+/// The number of [Foundation](PileRef::Foundation) piles in Calculation
+pub const NUM_FOUNDATIONS: usize = 4;
+
+/// The number of waste piles in Calculation, which are [Tableau](PileRef::Tableau) piles
+pub const NUM_WASTE: usize = 4;
+
+/// The [RuleSpec] for Calculation, see [GameRules] for their implementation
+pub const RULES: RuleSpec = RuleSpec {
+    name: "Calculation",
+    piles: &[
+        PileSpec {
+            kind: PileKind::Foundation,
+            count: NUM_FOUNDATIONS,
+            build: Build::UpByPosition,
+            matching: Matching::Any,
+            empty: Empty::Nothing,
+        },
+        PileSpec {
+            kind: PileKind::Tableau,
+            count: NUM_WASTE,
+            build: Build::Any,
+            matching: Matching::Any,
+            empty: Empty::Any,
+        },
+        PileSpec {
+            kind: PileKind::Stock,
+            count: 1,
+            build: Build::None,
+            matching: Matching::Any,
+            empty: Empty::Nothing,
+        },
+    ],
+    redeal: Redeal::None,
+};
+
+/// The mid-game "playing" [GameState] for Calculation
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlayingGameState {
+    /// The foundations, see [Foundation](PileRef::Foundation)
+    pub foundations: [Stack; NUM_FOUNDATIONS],
+    /// The waste piles, see [Tableau](PileRef::Tableau)
+    pub waste: [Stack; NUM_WASTE],
+    /// The stock, see [Stock](PileRef::Stock), with only the top card face up
+    pub stock: Stack,
+}
+
+impl GameState<Card, { Card::N }, PileRef> for PlayingGameState {
+    fn get_stack(&self, p: PileRef) -> Option<&Stack> {
+        match p {
+            PileRef::Foundation(n) => self.foundations.get(n),
+            PileRef::Tableau(n) => self.waste.get(n),
+            PileRef::Stock => Some(&self.stock),
+            PileRef::Talon => None,
+        }
+    }
+
+    fn get_stack_mut(&mut self, p: PileRef) -> Option<&mut Stack> {
+        match p {
+            PileRef::Foundation(n) => self.foundations.get_mut(n),
+            PileRef::Tableau(n) => self.waste.get_mut(n),
+            PileRef::Stock => Some(&mut self.stock),
+            PileRef::Talon => None,
+        }
+    }
+}
+
+impl GameStateExt<Card, { Card::N }, PileRef> for PlayingGameState {
+    fn piles(&self) -> Vec<PileRef> {
+        (0..NUM_FOUNDATIONS)
+            .map(PileRef::Foundation)
+            .chain((0..NUM_WASTE).map(PileRef::Tableau))
+            .chain([PileRef::Stock])
+            .collect()
+    }
+}
+
+/// The win [GameState] for Calculation, with every card on the foundations
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WinGameState {
+    pub foundations: [Stack; NUM_FOUNDATIONS],
+}
+
+/// Enum for the resulting [GameState] after making a move
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MoveResult {
+    Playing(PlayingGameState),
+    Win(WinGameState),
+}
+
+/// The Game rules for Calculation
+pub struct GameRules;
+
+impl GameRules {
+    /// Starts each foundation with the first card in the deck of its rank
+    /// (Ace, Two, Three and Four respectively), leaving the rest of the deck as the stock
+    pub fn deal(deck: Deck) -> PlayingGameState {
+        let mut stock = Stack::from_slice(&deck);
+        let foundations = [0, 1, 2, 3].map(|n| {
+            let rank = Rank::Ace.add_wrapping(n);
+            let i = stock.iter().position(|c| c.rank == rank).unwrap();
+            vec![Card {
+                face_up: true,
+                ..stock.remove(i)
+            }]
+        });
+        stock.flip_top();
+        PlayingGameState {
+            foundations,
+            waste: [(); NUM_WASTE].map(|_| Stack::new()),
+            stock,
+        }
+    }
+
+    /// Shuffles a new deck with the given [rand::Rng] and then [deal](Self::deal)s it
+    pub fn new_and_deal_with_rng<RNG: rand::Rng>(rng: &mut RNG) -> PlayingGameState {
+        let mut deck = Card::new_deck();
+        shuffle_with_rng(&mut deck, rng);
+        Self::deal(deck)
+    }
+
+    /// The rank that can next be placed on the foundation `n`, or [None] if it's complete.
+    /// Each foundation builds up by one more than the last, wrapping from King to Ace
+    pub fn next_rank(foundation: &Stack, n: usize) -> Option<Rank> {
+        match foundation.len() {
+            Rank::N => None,
+            _ => foundation.top().map(|c| c.rank.add_wrapping(n as u8 + 1)),
+        }
+    }
+
+    /// Attempts to move the top card of `src` onto `dst`, returning a copy of `state` with the
+    /// result of the move. Cards can be moved from the stock to any pile,
+    /// or from a waste pile to a foundation
+    pub fn move_card(state: PlayingGameState, src: PileRef, dst: PileRef) -> Result<MoveResult> {
+        // Validate src
+        match src {
+            PileRef::Stock | PileRef::Tableau(_) => {}
+            PileRef::Foundation(_) => {
+                return Err(Error::InvalidInput {
+                    field: "src",
+                    reason: "cannot move cards from foundation",
+                })
+            }
+            PileRef::Talon => {
+                return Err(Error::InvalidInput {
+                    field: "src",
+                    reason: "there is no talon",
+                })
+            }
+        }
+
+        // Validate dst
+        match (src, dst) {
+            (_, PileRef::Foundation(_)) | (PileRef::Stock, PileRef::Tableau(_)) => {}
+            (PileRef::Tableau(_), PileRef::Tableau(_)) => {
+                return Err(Error::InvalidInput {
+                    field: "dst",
+                    reason: "cannot move cards between waste piles",
+                })
+            }
+            _ => {
+                return Err(Error::InvalidInput {
+                    field: "dst",
+                    reason: "can only move cards to foundation or waste piles",
+                })
+            }
+        }
+
+        if state.get_stack(src).is_none() {
+            return Err(Error::InvalidInput {
+                field: "src",
+                reason: "pile doesn't exist",
+            });
+        }
+        if state.get_stack(dst).is_none() {
+            return Err(Error::InvalidInput {
+                field: "dst",
+                reason: "pile doesn't exist",
+            });
+        }
+
+        let card = match state.get_stack(src).and_then(|s| s.top()) {
+            Some(card) => *card,
+            None => {
+                return Err(Error::InvalidMove {
+                    reason: "source pile is empty",
+                })
+            }
+        };
+
+        if let PileRef::Foundation(n) = dst {
+            if Self::next_rank(&state.foundations[n], n) != Some(card.rank) {
+                return Err(Error::InvalidMove {
+                    reason: "card is not the next rank for the foundation",
+                });
+            }
+        }
+
+        let mut new_state = state;
+        let card = new_state.get_stack_mut(src).unwrap().pop().unwrap();
+        new_state.get_stack_mut(dst).unwrap().push(card);
+        new_state.stock.flip_top();
+
+        if new_state.foundations.iter().all(|f| f.len() == Rank::N) {
+            return Ok(MoveResult::Win(WinGameState {
+                foundations: new_state.foundations,
+            }));
+        }
+        Ok(MoveResult::Playing(new_state))
+    }
+}
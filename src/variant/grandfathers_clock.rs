@@ -0,0 +1,241 @@
+pub use common::{Card, Deck, FrenchSuit, PileRef, Rank, Stack, StackExt};
+
+use crate::{
+    common,
+    rules::{Build, Empty, Matching, PileKind, PileSpec, Redeal, RuleSpec},
+    shuffle_with_rng, GameState, GameStateExt,
+};
+pub use crate::{Card as CardTrait, Error, Result, StackFrom};
+
+/// The number of [Foundation](PileRef::Foundation) piles in Grandfather's Clock,
+/// one for each hour on the clock face
+pub const NUM_FOUNDATIONS: usize = 12;
+
+/// The number of [Tableau](PileRef::Tableau) piles in Grandfather's Clock
+pub const NUM_TABLEAU: usize = 8;
+
+/// The number of cards dealt to each [Tableau](PileRef::Tableau) pile,
+/// as the cards left after seeding the foundations are split evenly between them
+pub const TABLEAU_DEPTH: usize = (Card::N - NUM_FOUNDATIONS) / NUM_TABLEAU;
+
+/// The card each foundation is seeded with, by hour starting at one o'clock
+pub const SEEDS: [(Rank, FrenchSuit); NUM_FOUNDATIONS] = [
+    (Rank::Ten, FrenchSuit::Hearts),
+    (Rank::Jack, FrenchSuit::Spades),
+    (Rank::Queen, FrenchSuit::Diamonds),
+    (Rank::King, FrenchSuit::Clubs),
+    (Rank::Two, FrenchSuit::Hearts),
+    (Rank::Three, FrenchSuit::Spades),
+    (Rank::Four, FrenchSuit::Diamonds),
+    (Rank::Five, FrenchSuit::Clubs),
+    (Rank::Six, FrenchSuit::Hearts),
+    (Rank::Seven, FrenchSuit::Spades),
+    (Rank::Eight, FrenchSuit::Diamonds),
+    (Rank::Nine, FrenchSuit::Clubs),
+];
+
+/// The [RuleSpec] for Grandfather's Clock, see [GameRules] for their implementation
+pub const RULES: RuleSpec = RuleSpec {
+    name: "Grandfather's Clock",
+    piles: &[
+        PileSpec {
+            kind: PileKind::Foundation,
+            count: NUM_FOUNDATIONS,
+            build: Build::Up,
+            matching: Matching::Suit,
+            empty: Empty::Nothing,
+        },
+        PileSpec {
+            kind: PileKind::Tableau,
+            count: NUM_TABLEAU,
+            build: Build::Down,
+            matching: Matching::Any,
+            empty: Empty::Any,
+        },
+    ],
+    redeal: Redeal::None,
+};
+
+/// The mid-game "playing" [GameState] for Grandfather's Clock
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlayingGameState {
+    /// The foundations by hour, starting at one o'clock, see [Foundation](PileRef::Foundation)
+    pub foundations: [Stack; NUM_FOUNDATIONS],
+    /// The tableau, see [Tableau](PileRef::Tableau)
+    pub tableau: [Stack; NUM_TABLEAU],
+}
+
+impl GameState<Card, { Card::N }, PileRef> for PlayingGameState {
+    fn get_stack(&self, p: PileRef) -> Option<&Stack> {
+        match p {
+            PileRef::Foundation(n) => self.foundations.get(n),
+            PileRef::Tableau(n) => self.tableau.get(n),
+            _ => None,
+        }
+    }
+
+    fn get_stack_mut(&mut self, p: PileRef) -> Option<&mut Stack> {
+        match p {
+            PileRef::Foundation(n) => self.foundations.get_mut(n),
+            PileRef::Tableau(n) => self.tableau.get_mut(n),
+            _ => None,
+        }
+    }
+}
+
+impl GameStateExt<Card, { Card::N }, PileRef> for PlayingGameState {
+    fn piles(&self) -> Vec<PileRef> {
+        (0..NUM_FOUNDATIONS)
+            .map(PileRef::Foundation)
+            .chain((0..NUM_TABLEAU).map(PileRef::Tableau))
+            .collect()
+    }
+}
+
+/// The win [GameState] for Grandfather's Clock, with every foundation showing its hour
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WinGameState {
+    pub foundations: [Stack; NUM_FOUNDATIONS],
+}
+
+/// Enum for the resulting [GameState] after making a move
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MoveResult {
+    Playing(PlayingGameState),
+    Win(WinGameState),
+}
+
+/// The Game rules for Grandfather's Clock
+pub struct GameRules;
+
+impl GameRules {
+    /// Seeds each foundation with its card from [SEEDS],
+    /// then deals the rest of the deck face up across the tableau
+    pub fn deal(deck: Deck) -> PlayingGameState {
+        let mut rest: Stack = deck
+            .iter()
+            .filter(|c| !SEEDS.contains(&(c.rank, c.suit)))
+            .map(|c| Card {
+                face_up: true,
+                ..*c
+            })
+            .collect();
+        let foundations = SEEDS.map(|(rank, suit)| {
+            vec![Card {
+                rank,
+                suit,
+                face_up: true,
+            }]
+        });
+
+        let mut tableau = [(); NUM_TABLEAU].map(|_| Stack::new());
+        for (i, card) in rest.drain(..).enumerate() {
+            tableau[i % NUM_TABLEAU].push(card);
+        }
+        PlayingGameState {
+            foundations,
+            tableau,
+        }
+    }
+
+    /// Shuffles a new deck with the given [rand::Rng] and then [deal](Self::deal)s it
+    pub fn new_and_deal_with_rng<RNG: rand::Rng>(rng: &mut RNG) -> PlayingGameState {
+        let mut deck = Card::new_deck();
+        shuffle_with_rng(&mut deck, rng);
+        Self::deal(deck)
+    }
+
+    /// The rank the foundation `n` must end on to win, which is its hour on the clock face
+    /// (Jack for eleven o'clock and Queen for twelve o'clock)
+    pub fn target(n: usize) -> Rank {
+        Rank::Ace.add_wrapping(n as u8)
+    }
+
+    /// Whether the foundation `n` has been built up to its [target](Self::target)
+    pub fn is_complete(foundation: &Stack, n: usize) -> bool {
+        foundation.len() > 1 && foundation.top().map(|c| c.rank) == Some(Self::target(n))
+    }
+
+    /// Attempts to move the top card of the tableau pile `src` onto `dst`,
+    /// returning a copy of `state` with the result of the move
+    pub fn move_card(state: PlayingGameState, src: PileRef, dst: PileRef) -> Result<MoveResult> {
+        if !matches!(src, PileRef::Tableau(_)) {
+            return Err(Error::InvalidInput {
+                field: "src",
+                reason: "can only move cards from tableau",
+            });
+        }
+        if !matches!(dst, PileRef::Tableau(_) | PileRef::Foundation(_)) {
+            return Err(Error::InvalidInput {
+                field: "dst",
+                reason: "can only move cards to foundation or tableau",
+            });
+        }
+        if state.get_stack(src).is_none() {
+            return Err(Error::InvalidInput {
+                field: "src",
+                reason: "pile doesn't exist",
+            });
+        }
+        let dst_stack = match state.get_stack(dst) {
+            Some(s) => s,
+            None => {
+                return Err(Error::InvalidInput {
+                    field: "dst",
+                    reason: "pile doesn't exist",
+                })
+            }
+        };
+
+        // Source == destination is a no-op
+        if src == dst {
+            return Ok(MoveResult::Playing(state));
+        }
+
+        let card = match state.get_stack(src).and_then(|s| s.top()) {
+            Some(card) => *card,
+            None => {
+                return Err(Error::InvalidMove {
+                    reason: "source pile is empty",
+                })
+            }
+        };
+
+        match (dst, dst_stack.top()) {
+            (PileRef::Foundation(n), Some(top)) => {
+                if Self::is_complete(dst_stack, n) {
+                    return Err(Error::InvalidMove {
+                        reason: "foundation is already complete",
+                    });
+                }
+                if card.suit != top.suit || card.rank != top.rank.add_wrapping(1) {
+                    return Err(Error::InvalidMove {
+                        reason: "foundation must be built up in suit",
+                    });
+                }
+            }
+            (PileRef::Tableau(_), Some(top)) if top.rank.pred() != Some(card.rank) => {
+                return Err(Error::InvalidMove {
+                    reason: "tableau must be built down",
+                })
+            }
+            _ => {}
+        }
+
+        let mut new_state = state;
+        let card = new_state.get_stack_mut(src).unwrap().pop().unwrap();
+        new_state.get_stack_mut(dst).unwrap().push(card);
+
+        if new_state
+            .foundations
+            .iter()
+            .enumerate()
+            .all(|(n, f)| Self::is_complete(f, n))
+        {
+            return Ok(MoveResult::Win(WinGameState {
+                foundations: new_state.foundations,
+            }));
+        }
+        Ok(MoveResult::Playing(new_state))
+    }
+}
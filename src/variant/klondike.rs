@@ -1,8 +1,12 @@
-use std::cmp;
+use std::{cmp, fmt};
 
-pub use common::{Card, Color, Deck, FrenchSuit, PileRef, Rank, Stack};
+pub use common::{Card, Color, Deck, FoundationStack, FrenchSuit, PileRef, Rank, Stack, StackExt};
 
-use crate::{common, take_n_slice, take_n_vec_mut, take_one_vec_mut, GameState};
+use crate::{
+    common,
+    rules::{Build, Empty, Matching, PileKind, PileSpec, Redeal, RuleSpec},
+    take_n_vec_mut, take_one_vec_mut, GameState, GameStateExt,
+};
 pub use crate::{Card as CardTrait, Error, Result, StackFrom};
 
 /// The number of [Tableau](PileRef::Tableau) piles in Klondike Solitaire
@@ -32,6 +36,53 @@ pub type DealResult = common::DealResult<Card, { Card::N }, NUM_TABLEAU, NUM_FOU
 /// for Klondike Solitaire with [common::Card]
 pub type MoveResult = common::MoveResult<Card, { Card::N }, NUM_TABLEAU, NUM_FOUNDATIONS>;
 
+/// The [RuleSpec] for Klondike Solitaire, see [GameRules] for their implementation
+pub const RULES: RuleSpec = RuleSpec {
+    name: "Klondike",
+    piles: &[
+        PileSpec {
+            kind: PileKind::Tableau,
+            count: NUM_TABLEAU,
+            build: Build::Down,
+            matching: Matching::AlternateColor,
+            empty: Empty::Rank(Rank::King),
+        },
+        PileSpec {
+            kind: PileKind::Foundation,
+            count: NUM_FOUNDATIONS,
+            build: Build::Up,
+            matching: Matching::Suit,
+            empty: Empty::Rank(Rank::Ace),
+        },
+        PileSpec {
+            kind: PileKind::Stock,
+            count: 1,
+            build: Build::None,
+            matching: Matching::Any,
+            empty: Empty::Nothing,
+        },
+        PileSpec {
+            kind: PileKind::Talon,
+            count: 1,
+            build: Build::None,
+            matching: Matching::Any,
+            empty: Empty::Nothing,
+        },
+    ],
+    redeal: Redeal::Unlimited,
+};
+
+/// How a sequence of cards taken from a pile is checked, see [GameRules::valid_seq_with]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum SeqStrategy {
+    /// Every card must be valid to place on the card below it, see [GameRules::valid_seq]
+    #[default]
+    Strict,
+    /// Any face up run can be taken from the [Tableau](PileRef::Tableau),
+    /// as long as the joint with the destination is valid. A common casual mode relaxation
+    Relaxed,
+}
+
 /// The Game rules for Klondike Solitaire
 pub struct GameRules;
 
@@ -184,7 +235,7 @@ impl GameRules {
             PileRef::Tableau(_) => {
                 let mut prev_card = &cs[0];
                 for card in &cs[1..cs.len()] {
-                    if card.suit.color() == prev_card.suit.color() {
+                    if !card.is_opposite_color(prev_card) {
                         return false;
                     }
                     if prev_card.rank.next() != Some(&card.rank) {
@@ -212,6 +263,15 @@ impl GameRules {
         }
     }
 
+    /// Same as [valid_seq](Self::valid_seq), except with [Relaxed](SeqStrategy::Relaxed)
+    /// any face up sequence is valid for a [Tableau](PileRef::Tableau) pile
+    pub fn valid_seq_with(strategy: SeqStrategy, p: PileRef, cs: &[Card]) -> bool {
+        match (strategy, p) {
+            (SeqStrategy::Relaxed, PileRef::Tableau(_)) => cs.iter().all(|c| c.face_up),
+            _ => Self::valid_seq(p, cs),
+        }
+    }
+
     /// Attempts to move `take_n` [Card]s from the stack at `src` and place them onto `dst`,
     /// returning a copy of `state` with the result of the move.
     /// See [GameRules::valid_seq] for rules on what card sequences are valid to move.
@@ -232,6 +292,19 @@ impl GameRules {
         src: PileRef,
         take_n: usize,
         dst: PileRef,
+    ) -> Result<MoveResult> {
+        Self::move_cards_with(SeqStrategy::Strict, state, src, take_n, dst)
+    }
+
+    /// Same as [move_cards](Self::move_cards), except the cards taken from `src` are checked
+    /// with [valid_seq_with](Self::valid_seq_with) using the given [SeqStrategy].
+    /// The joint with `dst` is always checked strictly
+    pub fn move_cards_with(
+        strategy: SeqStrategy,
+        state: PlayingGameState,
+        src: PileRef,
+        take_n: usize,
+        dst: PileRef,
     ) -> Result<MoveResult> {
         if take_n == 0 {
             return Err(Error::InvalidInput {
@@ -290,88 +363,73 @@ impl GameRules {
         }
 
         // Create stacks for the new state of src and dst
-        let mut new_src_stack: Stack;
-        let new_dst_stack: Stack;
-        {
-            let src_stack = state.get_stack(src).ok_or(Error::InvalidInput {
+        let mut new_src_stack = state
+            .get_stack(src)
+            .ok_or(Error::InvalidInput {
                 field: "src",
                 reason: "pile does not exist",
-            })?;
+            })?
+            .clone();
 
-            if take_n > src_stack.len() {
-                return Err(Error::InvalidInput {
-                    field: "take_n",
-                    reason: "not enough cards in src pile",
-                });
-            }
-
-            let (rest, take) = take_n_slice(src_stack.as_slice(), take_n);
-            if !Self::valid_seq(src, take) {
-                return Err(Error::InvalidMove {
-                    reason: "src sequence is invalid",
-                });
-            }
-
-            new_src_stack = rest.iter().cloned().collect();
-            match new_src_stack.last_mut() {
-                Some(c) => c.face_up = true,
-                _ => {}
-            }
+        let take = new_src_stack
+            .take_top_n(take_n)
+            .ok_or(Error::InvalidInput {
+                field: "take_n",
+                reason: "not enough cards in src pile",
+            })?;
+        if !Self::valid_seq_with(strategy, src, &take) {
+            return Err(Error::InvalidMove {
+                reason: "src sequence is invalid",
+            });
+        }
+        new_src_stack.flip_top();
 
-            let dst_stack = state.get_stack(dst).ok_or(Error::InvalidInput {
+        let new_dst_stack = state
+            .get_stack(dst)
+            .ok_or(Error::InvalidInput {
                 field: "dst",
                 reason: "pile does not exist",
-            })?;
+            })?
+            .clone();
 
-            new_dst_stack = dst_stack.iter().chain(take.iter()).cloned().collect();
-
-            if dst_stack.is_empty() {
-                match dst {
-                    PileRef::Tableau(_) => {
-                        if take[0].rank != Rank::King {
-                            return Err(Error::InvalidMove {
-                                reason: "can only move a King to a space",
-                            });
-                        }
+        let new_dst_stack = match dst {
+            // Only a single card is moved to a foundation, see above
+            PileRef::Foundation(_) => {
+                let mut foundation = FoundationStack::try_from(new_dst_stack)?;
+                foundation.push(take[0]).map_err(|_| Error::InvalidMove {
+                    reason: "dst sequence is invalid",
+                })?;
+                foundation.into_inner()
+            }
+            _ => {
+                match new_dst_stack.top() {
+                    None if take[0].rank != Rank::King => {
+                        return Err(Error::InvalidMove {
+                            reason: "can only move a King to a space",
+                        })
                     }
-                    PileRef::Foundation(_) => {
-                        if take[0].rank != Rank::Ace {
-                            return Err(Error::InvalidMove {
-                                reason: "dst sequence is invalid",
-                            });
-                        }
+                    Some(&top) if !Self::valid_seq(dst, &[top, take[0]]) => {
+                        return Err(Error::InvalidMove {
+                            reason: "dst sequence is invalid",
+                        })
                     }
-                    PileRef::Stock => {}
-                    PileRef::Talon => {}
-                }
-            } else {
-                if !Self::valid_seq(
-                    dst,
-                    &new_dst_stack
-                        [new_dst_stack.len() - take_n - 1..new_dst_stack.len() - take_n + 1],
-                ) {
-                    return Err(Error::InvalidMove {
-                        reason: "dst sequence is invalid",
-                    });
+                    _ => {}
                 }
+                let mut new_dst_stack = new_dst_stack;
+                new_dst_stack.extend(take);
+                new_dst_stack
             }
-        }
+        };
 
         let mut new_state = state;
         *new_state.get_stack_mut(src).unwrap() = new_src_stack;
         *new_state.get_stack_mut(dst).unwrap() = new_dst_stack;
 
         match dst {
-            // If dst is a foundation, check for a win condition
-            PileRef::Foundation(_) => {
-                for foundation in &new_state.foundations {
-                    // Foundation doesn't have enough cards
-                    if foundation.len() < Rank::N {
-                        // So still playing
-                        return Ok(MoveResult::Playing(new_state));
-                    }
-                }
-                // All the foundations have the full suit, so return win state
+            // If dst is a foundation, check for a win condition,
+            // i.e. all the foundations have the full suit.
+            // Foundations are only built as a FoundationStack, so they're already in order
+            PileRef::Foundation(_) if new_state.foundations.iter().all(|f| f.len() == Rank::N) => {
                 Ok(MoveResult::Win(WinGameState {
                     foundations: new_state.foundations,
                 }))
@@ -384,6 +442,17 @@ impl GameRules {
         state: PlayingGameState,
         src: PileRef,
         take_n: usize,
+    ) -> Result<MoveResult> {
+        Self::auto_move_card_with(SeqStrategy::Strict, state, src, take_n)
+    }
+
+    /// Same as [auto_move_card](Self::auto_move_card), except the cards are moved with
+    /// [move_cards_with](Self::move_cards_with) using the given [SeqStrategy]
+    pub fn auto_move_card_with(
+        strategy: SeqStrategy,
+        state: PlayingGameState,
+        src: PileRef,
+        take_n: usize,
     ) -> Result<MoveResult> {
         match src {
             // No op
@@ -392,7 +461,7 @@ impl GameRules {
         }
 
         let try_move_cards = |dst| -> Result<Option<MoveResult>> {
-            match Self::move_cards(state.clone(), src, take_n, dst) {
+            match Self::move_cards_with(strategy, state.clone(), src, take_n, dst) {
                 Ok(result) => Ok(Some(result)),
                 // Return if there's a legitimate error (invalid input)
                 Err(err @ Error::InvalidInput { .. }) => Err(err),
@@ -426,4 +495,197 @@ impl GameRules {
         // No where to move the card, so no-op
         Ok(MoveResult::Playing(state))
     }
+
+    /// Moves the top card of `src` onto whichever [Foundation](PileRef::Foundation) accepts it.
+    /// Unlike [auto_move_card](Self::auto_move_card), this never falls back to the tableau,
+    /// and returns an [InvalidMove](Error::InvalidMove) if no foundation accepts the card
+    pub fn move_to_foundation(state: PlayingGameState, src: PileRef) -> Result<MoveResult> {
+        if let PileRef::Foundation(_) = src {
+            return Err(Error::InvalidInput {
+                field: "src",
+                reason: "card is already on a foundation",
+            });
+        }
+
+        for dst in (0..NUM_FOUNDATIONS).map(PileRef::Foundation) {
+            match Self::move_cards(state.clone(), src, 1, dst) {
+                Ok(result) => return Ok(result),
+                Err(err @ Error::InvalidInput { .. }) => return Err(err),
+                _ => {}
+            }
+        }
+
+        Err(Error::InvalidMove {
+            reason: "no foundation accepts the card",
+        })
+    }
+
+    /// Plays the opening moves that are always safe: Aces and Twos on top of the tableau
+    /// go onto the foundations, repeating while turning cards over reveals more of them.
+    /// Nothing else is moved, as other moves may block a win
+    pub fn play_opening(state: PlayingGameState) -> PlayingGameState {
+        let mut state = state;
+        loop {
+            let next = (0..NUM_TABLEAU).map(PileRef::Tableau).find_map(|p| {
+                match state.get_stack(p)?.top() {
+                    Some(c) if c.face_up && matches!(c.rank, Rank::Ace | Rank::Two) => {
+                        match Self::move_to_foundation(state.clone(), p) {
+                            Ok(MoveResult::Playing(next)) => Some(next),
+                            // Can't win with only Aces and Twos on the foundations
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                }
+            });
+            match next {
+                Some(next) => state = next,
+                None => return state,
+            }
+        }
+    }
+}
+
+/// The number of cards at the top of a [Tableau](PileRef::Tableau) pile that form a valid
+/// sequence, and so can be moved together. `0` if the pile is empty or its top card is face down
+pub fn movable_prefix(stack: &Stack) -> usize {
+    movable_prefix_with(SeqStrategy::Strict, stack)
+}
+
+/// Same as [movable_prefix], except the sequence is checked with
+/// [valid_seq_with](GameRules::valid_seq_with) using the given [SeqStrategy]
+pub fn movable_prefix_with(strategy: SeqStrategy, stack: &Stack) -> usize {
+    let (_, face_up) = stack.split_face_up();
+    if face_up.is_empty() {
+        return 0;
+    }
+    1 + face_up
+        .windows(2)
+        .rev()
+        .take_while(|w| GameRules::valid_seq_with(strategy, PileRef::Tableau(0), w))
+        .count()
+}
+
+/// A description of a single pile, see [describe_state]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PileDescription {
+    pub pile: PileRef,
+    /// The number of face down cards at the bottom of the pile
+    pub face_down: usize,
+    /// The face up cards, from the bottom of the pile to the top
+    pub face_up: Vec<Card>,
+    /// A plain text description of the pile, e.g. `tableau 3: 2 face down, 10♠ 9♥`
+    pub text: String,
+}
+
+/// A description of every pile in a [GameStateOption], see [describe_state]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BoardDescription {
+    pub piles: Vec<PileDescription>,
+}
+
+impl fmt::Display for BoardDescription {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, pile) in self.piles.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", pile.text)?;
+        }
+        Ok(())
+    }
+}
+
+/// Describes each pile of the given [GameStateOption], both as structured data
+/// and as plain text, for accessibility and tooling.
+/// Piles that don't exist in the state (e.g. the talon of an [InitialGameState]) are skipped
+pub fn describe_state(state: &GameStateOption) -> BoardDescription {
+    let piles = state
+        .piles()
+        .into_iter()
+        .filter_map(|pile| state.get_stack(pile).map(|stack| (pile, stack)))
+        .map(|(pile, stack)| {
+            let face_down = stack.iter().filter(|c| !c.face_up).count();
+            let face_up: Vec<_> = stack.iter().filter(|c| c.face_up).cloned().collect();
+
+            let name = match pile {
+                PileRef::Tableau(n) => format!("tableau {}", n + 1),
+                PileRef::Foundation(n) => format!("foundation {}", n + 1),
+                PileRef::Stock => "stock".to_string(),
+                PileRef::Talon => "talon".to_string(),
+            };
+            let mut parts = Vec::new();
+            if face_down > 0 {
+                parts.push(format!("{} face down", face_down));
+            }
+            if !face_up.is_empty() {
+                parts.push(
+                    face_up
+                        .iter()
+                        .map(|c| c.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                );
+            }
+            if parts.is_empty() {
+                parts.push("empty".to_string());
+            }
+
+            PileDescription {
+                pile,
+                face_down,
+                text: format!("{}: {}", name, parts.join(", ")),
+                face_up,
+            }
+        })
+        .collect();
+
+    BoardDescription { piles }
+}
+
+/// What the player can know about where a card is, see [count_cards]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CardStatus {
+    /// On a [Foundation](PileRef::Foundation)
+    Foundation,
+    /// Face up on the [Tableau](PileRef::Tableau) or [Talon](PileRef::Talon)
+    Visible,
+    /// Face down, or still in the [Stock](PileRef::Stock)
+    Unseen,
+}
+
+/// The [CardStatus] of every card in a game, see [count_cards]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CardCount {
+    statuses: [[CardStatus; Rank::N]; FrenchSuit::N],
+}
+
+impl CardCount {
+    /// The [CardStatus] of the card with the given [FrenchSuit] and [Rank]
+    pub fn get(&self, suit: FrenchSuit, rank: Rank) -> CardStatus {
+        self.statuses[suit as usize][rank as usize]
+    }
+
+    /// The number of cards with the given [CardStatus]
+    pub fn count(&self, status: CardStatus) -> usize {
+        self.statuses
+            .iter()
+            .flatten()
+            .filter(|s| **s == status)
+            .count()
+    }
+}
+
+/// Works out the [CardStatus] of every card in the given [GameStateOption],
+/// from what's visible to the player
+pub fn count_cards(state: &GameStateOption) -> CardCount {
+    let mut statuses = [[CardStatus::Unseen; Rank::N]; FrenchSuit::N];
+    for (pile, _, c) in state.cards() {
+        statuses[c.suit as usize][c.rank as usize] = match (pile, c.face_up) {
+            (PileRef::Foundation(_), _) => CardStatus::Foundation,
+            (_, true) => CardStatus::Visible,
+            (_, false) => CardStatus::Unseen,
+        };
+    }
+    CardCount { statuses }
 }
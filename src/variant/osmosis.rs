@@ -0,0 +1,276 @@
+use std::cmp;
+
+pub use common::{Card, Deck, FrenchSuit, PileRef, Rank, Stack, StackExt};
+
+use crate::{
+    common,
+    rules::{Build, Empty, Matching, PileKind, PileSpec, Redeal, RuleSpec},
+    shuffle_with_rng, take_n_vec_mut, GameState, GameStateExt,
+};
+pub use crate::{Card as CardTrait, Error, Result, StackFrom};
+
+/// The number of [Foundation](PileRef::Foundation) piles in Osmosis
+pub const NUM_FOUNDATIONS: usize = FrenchSuit::N;
+
+/// The number of reserve piles in Osmosis, which are [Tableau](PileRef::Tableau) piles
+pub const NUM_RESERVE: usize = 4;
+
+/// The number of cards dealt to each reserve pile
+pub const RESERVE_DEPTH: usize = 4;
+
+/// The number of cards drawn from the stock at a time in Osmosis.
+/// Treasure Trove is the same game drawing a single card at a time
+pub const DRAW_N: usize = 3;
+
+/// The [RuleSpec] for Osmosis, see [GameRules] for their implementation
+pub const RULES: RuleSpec = RuleSpec {
+    name: "Osmosis",
+    piles: &[
+        PileSpec {
+            kind: PileKind::Reserve,
+            count: NUM_RESERVE,
+            build: Build::None,
+            matching: Matching::Any,
+            empty: Empty::Nothing,
+        },
+        PileSpec {
+            kind: PileKind::Foundation,
+            count: NUM_FOUNDATIONS,
+            build: Build::RankInPrevious,
+            matching: Matching::Suit,
+            empty: Empty::BaseRank,
+        },
+        PileSpec {
+            kind: PileKind::Stock,
+            count: 1,
+            build: Build::None,
+            matching: Matching::Any,
+            empty: Empty::Nothing,
+        },
+        PileSpec {
+            kind: PileKind::Talon,
+            count: 1,
+            build: Build::None,
+            matching: Matching::Any,
+            empty: Empty::Nothing,
+        },
+    ],
+    redeal: Redeal::Unlimited,
+};
+
+/// The mid-game "playing" [GameState] for Osmosis
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlayingGameState {
+    /// The sealed reserve piles, with only the top card face up, see [Tableau](PileRef::Tableau)
+    pub reserve: [Stack; NUM_RESERVE],
+    /// The foundations, from top to bottom, see [Foundation](PileRef::Foundation)
+    pub foundations: [Stack; NUM_FOUNDATIONS],
+    /// The stock, see [Stock](PileRef::Stock)
+    pub stock: Stack,
+    /// The talon, see [Talon](PileRef::Talon)
+    pub talon: Stack,
+}
+
+impl GameState<Card, { Card::N }, PileRef> for PlayingGameState {
+    fn get_stack(&self, p: PileRef) -> Option<&Stack> {
+        match p {
+            PileRef::Tableau(n) => self.reserve.get(n),
+            PileRef::Foundation(n) => self.foundations.get(n),
+            PileRef::Stock => Some(&self.stock),
+            PileRef::Talon => Some(&self.talon),
+        }
+    }
+
+    fn get_stack_mut(&mut self, p: PileRef) -> Option<&mut Stack> {
+        match p {
+            PileRef::Tableau(n) => self.reserve.get_mut(n),
+            PileRef::Foundation(n) => self.foundations.get_mut(n),
+            PileRef::Stock => Some(&mut self.stock),
+            PileRef::Talon => Some(&mut self.talon),
+        }
+    }
+}
+
+impl GameStateExt<Card, { Card::N }, PileRef> for PlayingGameState {
+    fn piles(&self) -> Vec<PileRef> {
+        (0..NUM_RESERVE)
+            .map(PileRef::Tableau)
+            .chain((0..NUM_FOUNDATIONS).map(PileRef::Foundation))
+            .chain([PileRef::Stock, PileRef::Talon])
+            .collect()
+    }
+}
+
+/// The win [GameState] for Osmosis, with every card on the foundations
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WinGameState {
+    pub foundations: [Stack; NUM_FOUNDATIONS],
+}
+
+/// Enum for the resulting [GameState] after making a move
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MoveResult {
+    Playing(PlayingGameState),
+    Win(WinGameState),
+}
+
+/// The Game rules for Osmosis
+pub struct GameRules;
+
+impl GameRules {
+    /// Deals [RESERVE_DEPTH] cards to each reserve pile, turning the top one face up,
+    /// then the base card to the first foundation, leaving the rest of the deck as the stock
+    pub fn deal(deck: Deck) -> PlayingGameState {
+        let mut stock = Stack::from_slice(&deck);
+        let reserve = [(); NUM_RESERVE].map(|_| {
+            let mut pile = take_n_vec_mut(&mut stock, RESERVE_DEPTH);
+            pile.flip_top();
+            pile
+        });
+        let mut foundations = [(); NUM_FOUNDATIONS].map(|_| Stack::new());
+        foundations[0] = take_n_vec_mut(&mut stock, 1);
+        foundations[0].flip_top();
+        PlayingGameState {
+            reserve,
+            foundations,
+            stock,
+            talon: Stack::new(),
+        }
+    }
+
+    /// Shuffles a new deck with the given [rand::Rng] and then [deal](Self::deal)s it
+    pub fn new_and_deal_with_rng<RNG: rand::Rng>(rng: &mut RNG) -> PlayingGameState {
+        let mut deck = Card::new_deck();
+        shuffle_with_rng(&mut deck, rng);
+        Self::deal(deck)
+    }
+
+    /// Draws `n` cards from the [Stock](PileRef::Stock) onto the [Talon](PileRef::Talon).
+    /// If the stock is empty, the talon is turned over and used as the stock.
+    pub fn draw_stock(state: PlayingGameState, n: usize) -> Result<PlayingGameState> {
+        let mut new_state = state;
+        match new_state.stock.len() {
+            0 => {
+                (new_state.stock, new_state.talon) = (new_state.talon, new_state.stock);
+                for c in &mut new_state.stock {
+                    c.face_up = false;
+                }
+                new_state.stock.reverse();
+            }
+            len => {
+                let mut take = take_n_vec_mut(&mut new_state.stock, cmp::min(n, len));
+                for c in &mut take {
+                    c.face_up = true;
+                }
+                new_state.talon.append(&mut take);
+            }
+        }
+        Ok(new_state)
+    }
+
+    /// Checks whether `card` can be placed on the foundation `n`:
+    /// - The first foundation takes any card of its suit
+    /// - The other foundations take any card of their suit whose rank is already on the
+    ///   foundation above, or start with a card of the base card's rank and an unused suit.
+    ///   An empty foundation can be started before the ones above it
+    ///
+    /// Returns an [InvalidMove](Error::InvalidMove) describing the first check that failed
+    pub fn check_foundation(
+        foundations: &[Stack; NUM_FOUNDATIONS],
+        n: usize,
+        card: &Card,
+    ) -> Result<()> {
+        let base = match foundations[0].first() {
+            Some(base) => base,
+            None => {
+                return Err(Error::InvalidMove {
+                    reason: "first foundation has no base card",
+                })
+            }
+        };
+        match foundations[n].first() {
+            Some(f) if f.suit != card.suit => Err(Error::InvalidMove {
+                reason: "foundation must be built in suit",
+            }),
+            Some(_) if n > 0 && !foundations[n - 1].iter().any(|c| c.rank == card.rank) => {
+                Err(Error::InvalidMove {
+                    reason: "rank must already be on the foundation above",
+                })
+            }
+            Some(_) => Ok(()),
+            None if card.rank != base.rank => Err(Error::InvalidMove {
+                reason: "empty foundation must start with the base card's rank",
+            }),
+            None if foundations
+                .iter()
+                .filter_map(|f| f.first())
+                .any(|f| f.suit == card.suit) =>
+            {
+                Err(Error::InvalidMove {
+                    reason: "suit already has a foundation",
+                })
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Whether `card` can be placed on the foundation `n`, see [Self::check_foundation]
+    pub fn accepts(foundations: &[Stack; NUM_FOUNDATIONS], n: usize, card: &Card) -> bool {
+        Self::check_foundation(foundations, n, card).is_ok()
+    }
+
+    /// Attempts to move the top card of the [Talon](PileRef::Talon) or a reserve pile
+    /// onto the foundation `dst`, returning a copy of `state` with the result of the move
+    pub fn move_to_foundation(
+        state: PlayingGameState,
+        src: PileRef,
+        dst: usize,
+    ) -> Result<MoveResult> {
+        if !matches!(src, PileRef::Talon | PileRef::Tableau(_)) {
+            return Err(Error::InvalidInput {
+                field: "src",
+                reason: "can only move cards from talon or reserve",
+            });
+        }
+        if dst >= NUM_FOUNDATIONS {
+            return Err(Error::InvalidInput {
+                field: "dst",
+                reason: "foundation doesn't exist",
+            });
+        }
+        let card = match state.get_stack(src) {
+            None => {
+                return Err(Error::InvalidInput {
+                    field: "src",
+                    reason: "pile doesn't exist",
+                })
+            }
+            Some(s) => match s.top() {
+                Some(card) => *card,
+                None => {
+                    return Err(Error::InvalidMove {
+                        reason: "source pile is empty",
+                    })
+                }
+            },
+        };
+
+        Self::check_foundation(&state.foundations, dst, &card)?;
+
+        let mut new_state = state;
+        let src_stack = new_state.get_stack_mut(src).unwrap();
+        let card = src_stack.pop().unwrap();
+        // Unseal the next card of a reserve pile
+        if let PileRef::Tableau(_) = src {
+            src_stack.flip_top();
+        }
+        new_state.foundations[dst].push(card);
+
+        if new_state.foundations.iter().map(Vec::len).sum::<usize>() == Card::N {
+            return Ok(MoveResult::Win(WinGameState {
+                foundations: new_state.foundations,
+            }));
+        }
+        Ok(MoveResult::Playing(new_state))
+    }
+}
@@ -0,0 +1,253 @@
+//! Yukon and its relatives, which share a layout and differ only in how the tableau is built.
+//! Any face up cards can be moved together, whether or not they're in sequence
+
+use std::marker::PhantomData;
+
+pub use common::{Card, Deck, FoundationStack, FrenchSuit, PileRef, Rank, Stack, StackExt};
+
+use crate::{
+    common,
+    rules::{Build, Empty, Matching, PileKind, PileSpec, Redeal, RuleSpec},
+    shuffle_with_rng, GameState,
+};
+pub use crate::{Card as CardTrait, Error, Result, StackFrom};
+
+/// The number of [Tableau](PileRef::Tableau) piles in the Yukon family
+pub const NUM_TABLEAU: usize = 7;
+
+/// The number of [Foundation](PileRef::Foundation) piles in the Yukon family
+pub const NUM_FOUNDATIONS: usize = FrenchSuit::N;
+
+/// The number of face up cards dealt to every [Tableau](PileRef::Tableau) pile but the first
+pub const NUM_FACE_UP: usize = 5;
+
+/// The mid-game "playing" [GameState] for the Yukon family with [common::Card].
+/// The stock and talon are always empty
+pub type PlayingGameState =
+    common::PlayingGameState<Card, { Card::N }, NUM_TABLEAU, NUM_FOUNDATIONS>;
+
+/// The win [GameState] for the Yukon family with [common::Card]
+pub type WinGameState = common::WinGameState<Card, { Card::N }, NUM_FOUNDATIONS>;
+
+/// Enum for the resulting [GameState] after making a move,
+/// for the Yukon family with [common::Card]
+pub type MoveResult = common::MoveResult<Card, { Card::N }, NUM_TABLEAU, NUM_FOUNDATIONS>;
+
+/// How a member of the Yukon family builds its tableau
+pub trait Config {
+    const NAME: &'static str;
+    /// The rank order the tableau is built in, [Down](Build::Down) or [UpOrDown](Build::UpOrDown)
+    const BUILD: Build;
+    /// What a card must have in common with the card it's placed on in the tableau
+    const MATCHING: Matching;
+}
+
+/// Yukon, building down in alternating colors
+pub struct Yukon;
+
+impl Config for Yukon {
+    const NAME: &'static str = "Yukon";
+    const BUILD: Build = Build::Down;
+    const MATCHING: Matching = Matching::AlternateColor;
+}
+
+/// Russian Solitaire, building down in suit
+pub struct Russian;
+
+impl Config for Russian {
+    const NAME: &'static str = "Russian";
+    const BUILD: Build = Build::Down;
+    const MATCHING: Matching = Matching::Suit;
+}
+
+/// Alaska, building up or down in suit
+pub struct Alaska;
+
+impl Config for Alaska {
+    const NAME: &'static str = "Alaska";
+    const BUILD: Build = Build::UpOrDown;
+    const MATCHING: Matching = Matching::Suit;
+}
+
+/// The Game rules for the Yukon family, as given by the [Config]
+pub struct Rules<C: Config> {
+    config: PhantomData<C>,
+}
+
+impl<C: Config> Rules<C> {
+    /// The [RuleSpec] for the variant
+    pub const RULES: RuleSpec = RuleSpec {
+        name: C::NAME,
+        piles: &[
+            PileSpec {
+                kind: PileKind::Tableau,
+                count: NUM_TABLEAU,
+                build: C::BUILD,
+                matching: C::MATCHING,
+                empty: Empty::Rank(Rank::King),
+            },
+            PileSpec {
+                kind: PileKind::Foundation,
+                count: NUM_FOUNDATIONS,
+                build: Build::Up,
+                matching: Matching::Suit,
+                empty: Empty::Rank(Rank::Ace),
+            },
+        ],
+        redeal: Redeal::None,
+    };
+
+    /// Deals the whole deck to the tableau: a single face up card to the first pile,
+    /// then `n` face down cards followed by [NUM_FACE_UP] face up cards to pile `n`
+    pub fn deal(deck: Deck) -> PlayingGameState {
+        let mut cards = deck.into_iter();
+        let tableau: [Stack; NUM_TABLEAU] = std::array::from_fn(|n| {
+            let face_up = if n == 0 { 1 } else { NUM_FACE_UP };
+            (0..n + face_up)
+                .map(|i| Card {
+                    face_up: i >= n,
+                    ..cards.next().unwrap()
+                })
+                .collect()
+        });
+        PlayingGameState {
+            tableau,
+            foundations: [(); NUM_FOUNDATIONS].map(|_| Stack::new()),
+            stock: Stack::new(),
+            talon: Stack::new(),
+        }
+    }
+
+    /// Shuffles a new deck with the given [rand::Rng] and then [deal](Self::deal)s it
+    pub fn new_and_deal_with_rng<RNG: rand::Rng>(rng: &mut RNG) -> PlayingGameState {
+        let mut deck = Card::new_deck();
+        shuffle_with_rng(&mut deck, rng);
+        Self::deal(deck)
+    }
+
+    /// Whether `card` can be placed on `top` in the tableau, according to the [Config]
+    pub fn builds_on(top: &Card, card: &Card) -> bool {
+        let rank = match C::BUILD {
+            Build::Down => top.rank.pred() == Some(card.rank),
+            Build::Up => top.rank.succ() == Some(card.rank),
+            Build::UpOrDown => top.rank.distance(&card.rank) == 1,
+            Build::Any => true,
+            _ => false,
+        };
+        let matching = match C::MATCHING {
+            Matching::Any => true,
+            Matching::Suit => top.suit == card.suit,
+            Matching::AlternateColor => top.is_opposite_color(card),
+            Matching::SuitOrRank => top.suit == card.suit || top.rank == card.rank,
+        };
+        rank && matching
+    }
+
+    /// Attempts to move the top `take_n` cards of `src` onto `dst`,
+    /// returning a copy of `state` with the result of the move.
+    /// Any face up cards can be moved from the tableau, only the bottom one has to
+    /// [build on](Self::builds_on) `dst`
+    pub fn move_cards(
+        state: PlayingGameState,
+        src: PileRef,
+        take_n: usize,
+        dst: PileRef,
+    ) -> Result<MoveResult> {
+        if take_n == 0 {
+            return Err(Error::InvalidInput {
+                field: "take_n",
+                reason: "cannot take 0 cards",
+            });
+        }
+        match (src, dst) {
+            (PileRef::Stock | PileRef::Talon, _) => {
+                return Err(Error::InvalidInput {
+                    field: "src",
+                    reason: "can only move cards from tableau or foundation",
+                })
+            }
+            (_, PileRef::Stock | PileRef::Talon) => {
+                return Err(Error::InvalidInput {
+                    field: "dst",
+                    reason: "can only move cards to tableau or foundation",
+                })
+            }
+            (PileRef::Foundation(_), _) | (_, PileRef::Foundation(_)) if take_n != 1 => {
+                return Err(Error::InvalidInput {
+                    field: "take_n",
+                    reason: "cannot move more than 1 card to or from foundation",
+                })
+            }
+            _ => {}
+        }
+
+        // Source == destination is a no-op
+        if src == dst {
+            return Ok(MoveResult::Playing(state));
+        }
+
+        let mut new_src_stack = state
+            .get_stack(src)
+            .ok_or(Error::InvalidInput {
+                field: "src",
+                reason: "pile does not exist",
+            })?
+            .clone();
+        let take = new_src_stack
+            .take_top_n(take_n)
+            .ok_or(Error::InvalidInput {
+                field: "take_n",
+                reason: "not enough cards in src pile",
+            })?;
+        if !take.iter().all(|c| c.face_up) {
+            return Err(Error::InvalidMove {
+                reason: "cannot move face down cards",
+            });
+        }
+        new_src_stack.flip_top();
+
+        let new_dst_stack = state
+            .get_stack(dst)
+            .ok_or(Error::InvalidInput {
+                field: "dst",
+                reason: "pile does not exist",
+            })?
+            .clone();
+        let new_dst_stack = match dst {
+            PileRef::Foundation(_) => {
+                let mut foundation = FoundationStack::try_from(new_dst_stack)?;
+                foundation.push(take[0]).map_err(|_| Error::InvalidMove {
+                    reason: "dst sequence is invalid",
+                })?;
+                foundation.into_inner()
+            }
+            _ => {
+                let valid = match new_dst_stack.top() {
+                    None => take[0].rank == Rank::King,
+                    Some(top) => Self::builds_on(top, &take[0]),
+                };
+                if !valid {
+                    return Err(Error::InvalidMove {
+                        reason: "dst sequence is invalid",
+                    });
+                }
+                let mut new_dst_stack = new_dst_stack;
+                new_dst_stack.extend(take);
+                new_dst_stack
+            }
+        };
+
+        let mut new_state = state;
+        *new_state.get_stack_mut(src).unwrap() = new_src_stack;
+        *new_state.get_stack_mut(dst).unwrap() = new_dst_stack;
+
+        match dst {
+            PileRef::Foundation(_) if new_state.foundations.iter().all(|f| f.len() == Rank::N) => {
+                Ok(MoveResult::Win(WinGameState {
+                    foundations: new_state.foundations,
+                }))
+            }
+            _ => Ok(MoveResult::Playing(new_state)),
+        }
+    }
+}
@@ -1 +1,4 @@
 pub mod parse;
+pub mod random;
+#[cfg(feature = "proptest")]
+pub mod strategy;
@@ -1,53 +1,55 @@
-use lazy_static::lazy_static;
-use regex::Regex;
-use solitaire::common;
+use solitaire::{common, variant::klondike};
 
 pub fn rank(str: &str) -> common::Rank {
-    match str {
-        "A" | "1" => common::Rank::Ace,
-        "2" => common::Rank::Two,
-        "3" => common::Rank::Three,
-        "4" => common::Rank::Four,
-        "5" => common::Rank::Five,
-        "6" => common::Rank::Six,
-        "7" => common::Rank::Seven,
-        "8" => common::Rank::Eight,
-        "9" => common::Rank::Nine,
-        "X" => common::Rank::Ten,
-        "J" => common::Rank::Jack,
-        "Q" => common::Rank::Queen,
-        "K" => common::Rank::King,
-        &_ => panic!("unknown rank {}", str),
-    }
+    str.parse()
+        .unwrap_or_else(|_| panic!("unknown rank {}", str))
 }
 
 pub fn suit(str: &str) -> common::FrenchSuit {
-    match str {
-        "♣" | "C" => common::FrenchSuit::Clubs,
-        "♠" | "S" => common::FrenchSuit::Spades,
-        "♥" | "H" => common::FrenchSuit::Hearts,
-        "♦" | "D" => common::FrenchSuit::Diamonds,
-        &_ => panic!("unknown suit {}", str),
-    }
-}
-
-lazy_static! {
-    static ref CARD_PATTERN: Regex = Regex::new(r"^(?<h>#)?(?<r>.)(?<s>.)$").unwrap();
+    str.parse()
+        .unwrap_or_else(|_| panic!("unknown suit {}", str))
 }
 
 pub fn card(str: &str) -> common::Card {
-    let captures = CARD_PATTERN.captures(str).unwrap();
-    let (rank_str, suit_str) = (
-        captures.name("r").unwrap().as_str(),
-        captures.name("s").unwrap().as_str(),
-    );
-    common::Card {
-        suit: suit(suit_str),
-        rank: rank(rank_str),
-        face_up: captures.name("h") == None,
-    }
+    str.parse()
+        .unwrap_or_else(|_| panic!("invalid card {}", str))
 }
 
 pub fn cards(strs: &[&str]) -> Vec<common::Card> {
     strs.iter().map(|str| card(str)).collect()
 }
+
+/// Parses a multi-line board description into a [PlayingGameState](klondike::PlayingGameState).
+/// Each line is a pile followed by its cards (see [card]), from the bottom of the pile to the top.
+/// Piles that aren't listed are empty, e.g.
+/// ```text
+/// stock: #KC #AH
+/// talon: 2D
+/// foundation 1: AS 2S
+/// tableau 3: #9C #3H 7S 6H
+/// ```
+pub fn playing_state(str: &str) -> klondike::PlayingGameState {
+    let mut state = klondike::PlayingGameState {
+        tableau: [(); klondike::NUM_TABLEAU].map(|_| klondike::Stack::new()),
+        foundations: [(); klondike::NUM_FOUNDATIONS].map(|_| klondike::Stack::new()),
+        stock: klondike::Stack::new(),
+        talon: klondike::Stack::new(),
+    };
+
+    for line in str.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let (pile, cards_str) = line
+            .split_once(':')
+            .unwrap_or_else(|| panic!("expected `pile: cards`, got {}", line));
+        let index = |n: &str| n.trim().parse::<usize>().unwrap() - 1;
+        let pile = match pile.trim().split_once(' ') {
+            None if pile == "stock" => &mut state.stock,
+            None if pile == "talon" => &mut state.talon,
+            Some(("foundation", n)) => &mut state.foundations[index(n)],
+            Some(("tableau", n)) => &mut state.tableau[index(n)],
+            _ => panic!("unknown pile {}", pile),
+        };
+        *pile = cards(&cards_str.split_whitespace().collect::<Vec<_>>());
+    }
+
+    state
+}
@@ -0,0 +1,73 @@
+use rand::{seq::SliceRandom, Rng};
+use solitaire::{variant::klondike::*, GameState};
+
+/// A move a player can attempt, which may or may not be valid
+#[derive(Copy, Clone, Debug)]
+pub enum Move {
+    /// See [GameRules::draw_stock]
+    DrawStock(usize),
+    /// See [GameRules::move_cards]
+    MoveCards {
+        src: PileRef,
+        take_n: usize,
+        dst: PileRef,
+    },
+}
+
+impl Move {
+    /// Applies the move to the given state
+    pub fn apply(self, state: PlayingGameState) -> Result<MoveResult> {
+        match self {
+            Move::DrawStock(n) => GameRules::draw_stock(state, n).map(MoveResult::Playing),
+            Move::MoveCards { src, take_n, dst } => GameRules::move_cards(state, src, take_n, dst),
+        }
+    }
+}
+
+/// Deals a new game and plays up to `n_moves` random legal moves, for a realistic mid-game
+/// [PlayingGameState]. Stops early if there are no legal moves left, and never plays a
+/// winning move
+pub fn random_playing_state<R: Rng>(rng: &mut R, n_moves: usize) -> PlayingGameState {
+    let mut state = GameRules::new_and_deal_with_rng(rng);
+    for _ in 0..n_moves {
+        let moves = legal_moves(&state);
+        match moves.choose(rng) {
+            Some((_, next)) => state = next.clone(),
+            None => break,
+        }
+    }
+    state
+}
+
+/// Finds every legal [Move] (drawing one card at a time) that doesn't win the game,
+/// along with the resulting state
+pub fn legal_moves(state: &PlayingGameState) -> Vec<(Move, PlayingGameState)> {
+    let piles: Vec<_> = [PileRef::Talon]
+        .into_iter()
+        .chain((0..NUM_FOUNDATIONS).map(PileRef::Foundation))
+        .chain((0..NUM_TABLEAU).map(PileRef::Tableau))
+        .collect();
+
+    let mut candidates = vec![Move::DrawStock(1)];
+    for &src in piles.iter() {
+        let face_up = state
+            .get_stack(src)
+            .unwrap()
+            .iter()
+            .filter(|c| c.face_up)
+            .count();
+        for take_n in 1..=face_up {
+            for &dst in piles.iter().filter(|&&dst| dst != src) {
+                candidates.push(Move::MoveCards { src, take_n, dst });
+            }
+        }
+    }
+
+    candidates
+        .into_iter()
+        .filter_map(|m| match m.apply(state.clone()) {
+            Ok(MoveResult::Playing(next)) if &next != state => Some((m, next)),
+            _ => None,
+        })
+        .collect()
+}
@@ -0,0 +1,69 @@
+//! [proptest] strategies for cards, and Klondike decks, game states and moves
+
+use proptest::prelude::*;
+use solitaire::variant::klondike::*;
+
+use crate::random::Move;
+
+/// Any [FrenchSuit]
+pub fn suit() -> impl Strategy<Value = FrenchSuit> {
+    prop::sample::select(&FrenchSuit::VALUES[..])
+}
+
+/// Any [Rank]
+pub fn rank() -> impl Strategy<Value = Rank> {
+    prop::sample::select(&Rank::VALUES[..])
+}
+
+/// Any [Card], face up or face down
+pub fn card() -> impl Strategy<Value = Card> {
+    (suit(), rank(), any::<bool>()).prop_map(|(suit, rank, face_up)| Card {
+        suit,
+        rank,
+        face_up,
+    })
+}
+
+/// A shuffled [Deck]
+pub fn deck() -> impl Strategy<Value = Deck> {
+    Just(Card::new_deck().to_vec())
+        .prop_shuffle()
+        .prop_map(|cards| cards.try_into().unwrap())
+}
+
+/// Any valid [PileRef] for Klondike
+pub fn pile_ref() -> impl Strategy<Value = PileRef> {
+    prop_oneof![
+        Just(PileRef::Stock),
+        Just(PileRef::Talon),
+        (0..NUM_FOUNDATIONS).prop_map(PileRef::Foundation),
+        (0..NUM_TABLEAU).prop_map(PileRef::Tableau),
+    ]
+}
+
+/// Any [Move], including invalid ones
+pub fn any_move() -> impl Strategy<Value = Move> {
+    prop_oneof![
+        (1..=3usize).prop_map(Move::DrawStock),
+        (pile_ref(), 1..=Rank::N, pile_ref()).prop_map(|(src, take_n, dst)| Move::MoveCards {
+            src,
+            take_n,
+            dst
+        }),
+    ]
+}
+
+/// A [PlayingGameState] reachable from a fresh deal, by attempting a sequence of moves
+/// and skipping the invalid ones
+pub fn playing_state() -> impl Strategy<Value = PlayingGameState> {
+    (deck(), prop::collection::vec(any_move(), 0..256)).prop_map(|(deck, moves)| {
+        let mut state = GameRules::deal_all(InitialGameState::from(deck));
+        for m in moves {
+            // Stop short of winning, so the state is still playing
+            if let Ok(MoveResult::Playing(next)) = m.apply(state.clone()) {
+                state = next;
+            }
+        }
+        state
+    })
+}
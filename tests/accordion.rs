@@ -0,0 +1,2 @@
+#[path = "variant/accordion.rs"]
+pub mod accordion;
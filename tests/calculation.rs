@@ -0,0 +1,2 @@
+#[path = "variant/calculation.rs"]
+pub mod calculation;
@@ -33,3 +33,21 @@ fn test_take_one() {
         assert_eq!(c, d[51]);
     }
 }
+
+#[test]
+fn test_shuffle_reproducible() {
+    let mut d: common::Deck = common::Card::new_deck();
+    shuffle_with_rng(&mut d, &mut seed::rng_from_u64(0));
+    // If this changes, every seeded deal has changed too
+    assert_eq!(
+        d[..5],
+        test_util::parse::cards(&["#QS", "#AC", "#7H", "#QD", "#5S"])
+    );
+
+    let mut d: common::Deck = common::Card::new_deck();
+    shuffle_with_rng(&mut d, &mut seed::rng_from_str("klondike"));
+    assert_eq!(
+        d[..5],
+        test_util::parse::cards(&["#9D", "#JS", "#XH", "#4C", "#7D"])
+    );
+}
@@ -1,2 +1,8 @@
 #[path = "common/card.rs"]
 pub mod card;
+
+#[path = "common/stack.rs"]
+pub mod stack;
+
+#[path = "common/game_state.rs"]
+pub mod game_state;
@@ -26,3 +26,128 @@ fn test_ordering() {
     assert!(c1 > c2);
     assert!(c2 < c1);
 }
+
+#[test]
+fn test_display_from_str() {
+    let card = common::Card {
+        suit: common::FrenchSuit::Spades,
+        rank: common::Rank::Ten,
+        face_up: true,
+    };
+    assert_eq!(card.to_string(), "10♠");
+    assert_eq!("10♠".parse(), Ok(card));
+    assert_eq!("XS".parse(), Ok(card));
+
+    let card = common::Card {
+        suit: common::FrenchSuit::Hearts,
+        rank: common::Rank::Ace,
+        face_up: false,
+    };
+    assert_eq!(card.to_string(), "#A♥");
+    assert_eq!("#A♥".parse(), Ok(card));
+    assert_eq!("#1H".parse(), Ok(card));
+
+    // Every card round trips
+    for card in common::Card::new_deck() {
+        assert_eq!(card.to_string().parse(), Ok(card));
+    }
+
+    assert!("".parse::<common::Card>().is_err());
+    assert!("#".parse::<common::Card>().is_err());
+    assert!("11♠".parse::<common::Card>().is_err());
+    assert!("KZ".parse::<common::Card>().is_err());
+}
+
+#[test]
+fn test_rank_arithmetic() {
+    use common::Rank;
+
+    assert_eq!(Rank::Ace.value(), 1);
+    assert_eq!(Rank::Ten.value(), 10);
+    assert_eq!(Rank::King.value(), 13);
+
+    assert_eq!(Rank::Ace.succ(), Some(Rank::Two));
+    assert_eq!(Rank::Queen.succ(), Some(Rank::King));
+    assert_eq!(Rank::King.succ(), None);
+
+    assert_eq!(Rank::Two.pred(), Some(Rank::Ace));
+    assert_eq!(Rank::King.pred(), Some(Rank::Queen));
+    assert_eq!(Rank::Ace.pred(), None);
+
+    assert_eq!(Rank::Ace.distance(&Rank::Ace), 0);
+    assert_eq!(Rank::Three.distance(&Rank::Seven), 4);
+    assert_eq!(Rank::Seven.distance(&Rank::Three), 4);
+    assert_eq!(Rank::Ace.distance(&Rank::King), 12);
+
+    assert_eq!(Rank::Ace.add_wrapping(0), Rank::Ace);
+    assert_eq!(Rank::Ace.add_wrapping(3), Rank::Four);
+    assert_eq!(Rank::Jack.add_wrapping(2), Rank::King);
+    assert_eq!(Rank::Queen.add_wrapping(2), Rank::Ace);
+    assert_eq!(Rank::King.add_wrapping(4), Rank::Four);
+    assert_eq!(Rank::Five.add_wrapping(13), Rank::Five);
+
+    assert!(Rank::Nine.adjacent_wrapping(&Rank::Ten));
+    assert!(Rank::Ten.adjacent_wrapping(&Rank::Nine));
+    assert!(Rank::King.adjacent_wrapping(&Rank::Ace));
+    assert!(Rank::Ace.adjacent_wrapping(&Rank::King));
+    assert!(!Rank::Ace.adjacent_wrapping(&Rank::Ace));
+    assert!(!Rank::Ace.adjacent_wrapping(&Rank::Three));
+
+    // Every rank is adjacent to exactly two others
+    for r in Rank::VALUES {
+        let n = Rank::VALUES
+            .iter()
+            .filter(|o| r.adjacent_wrapping(o))
+            .count();
+        assert_eq!(n, 2);
+    }
+}
+
+#[test]
+fn test_suit_colors() {
+    use common::{Color, FrenchSuit};
+
+    assert_eq!(FrenchSuit::iter().count(), FrenchSuit::N);
+    assert_eq!(
+        Color::Black.suits().collect::<Vec<_>>(),
+        vec![FrenchSuit::Clubs, FrenchSuit::Spades]
+    );
+    assert_eq!(
+        Color::Red.suits().collect::<Vec<_>>(),
+        vec![FrenchSuit::Hearts, FrenchSuit::Diamonds]
+    );
+
+    let card = |suit| common::Card {
+        suit,
+        rank: common::Rank::Ace,
+        face_up: true,
+    };
+    assert!(card(FrenchSuit::Clubs).is_opposite_color(&card(FrenchSuit::Hearts)));
+    assert!(card(FrenchSuit::Diamonds).is_opposite_color(&card(FrenchSuit::Spades)));
+    assert!(!card(FrenchSuit::Clubs).is_opposite_color(&card(FrenchSuit::Spades)));
+    assert!(!card(FrenchSuit::Hearts).is_opposite_color(&card(FrenchSuit::Hearts)));
+}
+
+#[test]
+fn test_build_deck() {
+    use common::FrenchSuit;
+
+    // All suits once is the same as a new deck
+    let d: common::Deck = common::Card::new_deck();
+    assert_eq!(common::build_deck(&FrenchSuit::VALUES, 1), d.to_vec());
+
+    // Two suit Spider
+    let d = common::build_deck(&[FrenchSuit::Spades, FrenchSuit::Hearts], 4);
+    assert_eq!(d.len(), 104);
+    assert_eq!(
+        d.iter().filter(|c| c.suit == FrenchSuit::Spades).count(),
+        52
+    );
+    assert_eq!(
+        d.iter().filter(|c| c.suit == FrenchSuit::Hearts).count(),
+        52
+    );
+    assert!(d.iter().all(|c| !c.face_up));
+
+    assert!(common::build_deck(&FrenchSuit::VALUES, 0).is_empty());
+}
@@ -0,0 +1,88 @@
+use solitaire::variant::klondike::{
+    GameStateOption, PileRef, PlayingGameState, Stack, WinGameState,
+};
+use solitaire::GameStateExt;
+use test_util::parse;
+
+fn playing() -> PlayingGameState {
+    PlayingGameState {
+        tableau: [
+            parse::cards(&["#KS", "QH"]),
+            parse::cards(&["#2C", "#3C", "JC"]),
+            Stack::new(),
+            Stack::new(),
+            Stack::new(),
+            Stack::new(),
+            parse::cards(&["9D"]),
+        ],
+        foundations: [
+            parse::cards(&["AH", "2H"]),
+            parse::cards(&["AS"]),
+            Stack::new(),
+            Stack::new(),
+        ],
+        stock: parse::cards(&["#7C", "#8C", "#XC"]),
+        talon: parse::cards(&["4D"]),
+    }
+}
+
+#[test]
+fn test_face_down_count() {
+    assert_eq!(playing().face_down_count(), 3);
+}
+
+#[test]
+fn test_foundation_count() {
+    assert_eq!(playing().foundation_count(), 3);
+}
+
+#[test]
+fn test_stock_len() {
+    assert_eq!(playing().stock_len(), 3);
+}
+
+#[test]
+fn test_is_empty() {
+    let state = playing();
+    assert!(!state.is_empty(PileRef::Tableau(0)));
+    assert!(state.is_empty(PileRef::Tableau(2)));
+    assert!(!state.is_empty(PileRef::Foundation(1)));
+    assert!(state.is_empty(PileRef::Foundation(2)));
+    assert!(!state.is_empty(PileRef::Talon));
+    // Out of range piles don't exist, so are empty
+    assert!(state.is_empty(PileRef::Tableau(7)));
+}
+
+#[test]
+fn test_piles() {
+    let piles = playing().piles();
+    assert_eq!(piles.len(), 2 + 4 + 7);
+    assert_eq!(
+        piles[..3],
+        [PileRef::Stock, PileRef::Talon, PileRef::Foundation(0)]
+    );
+    assert_eq!(piles.last(), Some(&PileRef::Tableau(6)));
+
+    let win = GameStateOption::from(WinGameState {
+        foundations: [(); 4].map(|_| Stack::new()),
+    });
+    assert_eq!(
+        win.piles(),
+        (0..4).map(PileRef::Foundation).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_cards() {
+    let state = playing();
+    let cards: Vec<_> = state.cards().collect();
+    assert_eq!(cards.len(), 13);
+    assert_eq!(cards[0], (PileRef::Stock, 0, &parse::card("#7C")));
+    assert_eq!(cards[3], (PileRef::Talon, 0, &parse::card("4D")));
+    assert_eq!(cards[5], (PileRef::Foundation(0), 1, &parse::card("2H")));
+    assert_eq!(cards[10], (PileRef::Tableau(1), 1, &parse::card("#3C")));
+    assert_eq!(
+        cards.last(),
+        Some(&(PileRef::Tableau(6), 0, &parse::card("9D")))
+    );
+}
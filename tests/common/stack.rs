@@ -0,0 +1,93 @@
+use solitaire::common::StackExt;
+use test_util::parse;
+
+#[test]
+fn test_top() {
+    assert_eq!(parse::cards(&["#KS", "QH"]).top(), Some(&parse::card("QH")));
+    assert_eq!(parse::cards(&[]).top(), None);
+}
+
+#[test]
+fn test_take_top_n() {
+    let mut stack = parse::cards(&["#KS", "QH", "JC"]);
+
+    assert_eq!(stack.take_top_n(4), None);
+    assert_eq!(stack.len(), 3);
+
+    assert_eq!(stack.take_top_n(2), Some(parse::cards(&["QH", "JC"])));
+    assert_eq!(stack, parse::cards(&["#KS"]));
+
+    assert_eq!(stack.take_top_n(0), Some(vec![]));
+    assert_eq!(stack.take_top_n(1), Some(parse::cards(&["#KS"])));
+    assert!(stack.is_empty());
+}
+
+#[test]
+fn test_flip_top() {
+    let mut stack = parse::cards(&["#KS", "#QH"]);
+    assert!(stack.flip_top());
+    assert_eq!(stack, parse::cards(&["#KS", "QH"]));
+
+    // Already face up
+    assert!(!stack.flip_top());
+    assert_eq!(stack, parse::cards(&["#KS", "QH"]));
+
+    assert!(!parse::cards(&[]).flip_top());
+}
+
+#[test]
+fn test_split_face_up() {
+    let stack = parse::cards(&["#KS", "#QH", "9C", "8D"]);
+    assert_eq!(stack.split_face_up(), (&stack[..2], &stack[2..]));
+
+    let stack = parse::cards(&["KS", "QH"]);
+    assert_eq!(stack.split_face_up(), (&stack[..0], &stack[..]));
+
+    let stack = parse::cards(&["KS", "#QH"]);
+    assert_eq!(stack.split_face_up(), (&stack[..], &stack[2..]));
+}
+
+#[test]
+fn test_is_descending_alt_color() {
+    assert!(parse::cards(&["KS", "QH", "JC", "XD"]).is_descending_alt_color());
+    assert!(parse::cards(&["5H"]).is_descending_alt_color());
+    assert!(parse::cards(&[]).is_descending_alt_color());
+
+    // Same color
+    assert!(!parse::cards(&["KS", "QC"]).is_descending_alt_color());
+    // Not descending
+    assert!(!parse::cards(&["QH", "KS"]).is_descending_alt_color());
+    assert!(!parse::cards(&["KS", "JH"]).is_descending_alt_color());
+}
+
+#[test]
+fn test_foundation_stack() -> solitaire::Result<()> {
+    use solitaire::{common::FoundationStack, Error};
+
+    let mut foundation = FoundationStack::new();
+    let invalid = Err(Error::InvalidMove {
+        reason: "foundation must be built up in suit from Ace",
+    });
+    assert_eq!(foundation.push(parse::card("2S")), invalid);
+    foundation.push(parse::card("AS"))?;
+    foundation.push(parse::card("2S"))?;
+    // Wrong suit, or not the next rank
+    assert_eq!(foundation.push(parse::card("3H")), invalid);
+    assert_eq!(foundation.push(parse::card("4S")), invalid);
+    assert_eq!(*foundation, parse::cards(&["AS", "2S"]));
+    assert!(!foundation.is_complete());
+
+    // Out of order stacks are caught on conversion
+    assert_eq!(
+        FoundationStack::try_from(parse::cards(&["AH", "3H"])),
+        Err(Error::InvalidMove {
+            reason: "foundation must be built up in suit from Ace",
+        })
+    );
+    let full = FoundationStack::try_from(parse::cards(&[
+        "AH", "2H", "3H", "4H", "5H", "6H", "7H", "8H", "9H", "XH", "JH", "QH", "KH",
+    ]))?;
+    assert!(full.is_complete());
+    assert_eq!(full.into_inner().len(), 13);
+    Ok(())
+}
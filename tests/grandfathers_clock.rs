@@ -0,0 +1,2 @@
+#[path = "variant/grandfathers_clock.rs"]
+pub mod grandfathers_clock;
@@ -1,2 +1,5 @@
 #[path = "variant/klondike.rs"]
 pub mod klondike;
+
+#[path = "variant/klondike_properties.rs"]
+pub mod klondike_properties;
@@ -0,0 +1,2 @@
+#[path = "variant/osmosis.rs"]
+pub mod osmosis;
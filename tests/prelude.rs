@@ -0,0 +1,11 @@
+use solitaire::prelude::*;
+
+#[test]
+fn test_prelude() {
+    let state = GameRules::deal_all(InitialGameState::from(Card::new_deck()));
+    assert_eq!(state.get_stack(PileRef::Tableau(6)).unwrap().len(), 7);
+    assert_eq!(state.stock_len(), Card::N - 28);
+
+    let state = GameRules::draw_stock(state, 1).unwrap();
+    assert!(state.get_stack(PileRef::Talon).unwrap().top().is_some());
+}
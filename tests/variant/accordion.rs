@@ -0,0 +1,93 @@
+use solitaire::variant::accordion::*;
+use test_util::parse;
+
+fn playing(piles: &[&[&str]]) -> PlayingGameState {
+    PlayingGameState {
+        piles: piles.iter().map(|p| parse::cards(p)).collect(),
+    }
+}
+
+#[test]
+fn test_game_rules_deal() {
+    let deck: Deck = Card::new_deck();
+    let game = GameRules::deal(deck);
+    assert_eq!(game.piles.len(), Card::N);
+    for (pile, card) in game.piles.iter().zip(deck.iter()) {
+        assert_eq!(pile.len(), 1);
+        assert_eq!(pile[0].rank, card.rank);
+        assert_eq!(pile[0].suit, card.suit);
+        assert!(pile[0].face_up);
+    }
+}
+
+#[test]
+fn test_game_rules_move_pile() -> Result<()> {
+    let game = playing(&[&["KS"], &["4H"], &["9D"], &["KH"], &["2H"]]);
+
+    // K♥ goes onto K♠ three places left, by rank
+    let game = match GameRules::move_pile(game, 3, Distance::Three)? {
+        MoveResult::Playing(game) => game,
+        MoveResult::Win(_) => panic!(),
+    };
+    assert_eq!(game, playing(&[&["KS", "KH"], &["4H"], &["9D"], &["2H"]]));
+
+    // 2♥ can't go onto 9♦
+    assert_eq!(
+        GameRules::move_pile(game.clone(), 3, Distance::One),
+        Err(Error::InvalidMove {
+            reason: "top cards must match in suit or rank"
+        })
+    );
+    // But can go three places left onto K♥, by suit
+    let game = match GameRules::move_pile(game, 3, Distance::Three)? {
+        MoveResult::Playing(game) => game,
+        MoveResult::Win(_) => panic!(),
+    };
+    assert_eq!(game, playing(&[&["KS", "KH", "2H"], &["4H"], &["9D"]]));
+
+    // There are no piles far enough to the left, or at all
+    assert!(matches!(
+        GameRules::move_pile(game.clone(), 1, Distance::Three),
+        Err(Error::InvalidInput { .. })
+    ));
+    assert!(matches!(
+        GameRules::move_pile(game, 3, Distance::One),
+        Err(Error::InvalidInput { .. })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_game_rules_move_pile_win() -> Result<()> {
+    let game = playing(&[&["KS", "KH"], &["2H"]]);
+    assert_eq!(
+        GameRules::move_pile(game, 1, Distance::One)?,
+        MoveResult::Win(WinGameState {
+            pile: parse::cards(&["KS", "KH", "2H"])
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_game_rules_valid_moves() {
+    let game = playing(&[&["KS"], &["4H"], &["9D"], &["KH"], &["2H"]]);
+    assert_eq!(
+        GameRules::valid_moves(&game),
+        vec![
+            (3, Distance::Three),
+            (4, Distance::One),
+            (4, Distance::Three)
+        ]
+    );
+}
+
+#[test]
+fn test_rules() {
+    assert_eq!(
+        RULES.to_string(),
+        "Accordion
+- tableau (52): builds in any order matching suit or rank, empty piles stay empty"
+    );
+}
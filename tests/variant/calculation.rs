@@ -0,0 +1,154 @@
+use solitaire::variant::calculation::*;
+use test_util::parse;
+
+fn playing(foundations: [&[&str]; 4], waste: [&[&str]; 4], stock: &[&str]) -> PlayingGameState {
+    PlayingGameState {
+        foundations: foundations.map(parse::cards),
+        waste: waste.map(parse::cards),
+        stock: parse::cards(stock),
+    }
+}
+
+#[test]
+fn test_game_rules_deal() {
+    let deck: Deck = Card::new_deck();
+    let game = GameRules::deal(deck);
+
+    for (n, f) in game.foundations.iter().enumerate() {
+        assert_eq!(f.len(), 1);
+        assert_eq!(f[0].rank.value() as usize, n + 1);
+        assert!(f[0].face_up);
+    }
+    assert!(game.waste.iter().all(|w| w.is_empty()));
+    assert_eq!(game.stock.len(), Card::N - NUM_FOUNDATIONS);
+    let (face_down, face_up) = game.stock.split_face_up();
+    assert_eq!((face_down.len(), face_up.len()), (Card::N - 5, 1));
+}
+
+#[test]
+fn test_game_rules_next_rank() {
+    assert_eq!(
+        GameRules::next_rank(&parse::cards(&["AC"]), 0),
+        Some(Rank::Two)
+    );
+    assert_eq!(
+        GameRules::next_rank(&parse::cards(&["2C", "4H", "6S", "8D", "XC", "QH"]), 1),
+        Some(Rank::Ace)
+    );
+    assert_eq!(
+        GameRules::next_rank(&parse::cards(&["4C", "8H", "QS"]), 3),
+        Some(Rank::Three)
+    );
+    let complete = [
+        "3C", "6C", "9C", "QC", "2C", "5C", "8C", "JC", "AC", "4C", "7C", "XC", "KC",
+    ];
+    assert_eq!(GameRules::next_rank(&parse::cards(&complete), 2), None);
+}
+
+#[test]
+fn test_game_rules_move_card() -> Result<()> {
+    let game = playing(
+        [&["AC"], &["2C"], &["3C"], &["4C"]],
+        [&[], &[], &[], &["6H"]],
+        &["#9D", "#8S", "5D"],
+    );
+
+    // 5 can't go onto the Ace foundation, but can be placed on a waste pile
+    assert_eq!(
+        GameRules::move_card(game.clone(), PileRef::Stock, PileRef::Foundation(0)),
+        Err(Error::InvalidMove {
+            reason: "card is not the next rank for the foundation"
+        })
+    );
+    let game = match GameRules::move_card(game, PileRef::Stock, PileRef::Tableau(0))? {
+        MoveResult::Playing(game) => game,
+        MoveResult::Win(_) => panic!(),
+    };
+    assert_eq!(
+        game,
+        playing(
+            [&["AC"], &["2C"], &["3C"], &["4C"]],
+            [&["5D"], &[], &[], &["6H"]],
+            &["#9D", "8S"],
+        )
+    );
+
+    // 8 goes onto 4 foundation, then the 6 from the waste pile goes onto the 3 foundation
+    let game = match GameRules::move_card(game, PileRef::Stock, PileRef::Foundation(3))? {
+        MoveResult::Playing(game) => game,
+        MoveResult::Win(_) => panic!(),
+    };
+    let game = match GameRules::move_card(game, PileRef::Tableau(3), PileRef::Foundation(2))? {
+        MoveResult::Playing(game) => game,
+        MoveResult::Win(_) => panic!(),
+    };
+    assert_eq!(
+        game,
+        playing(
+            [&["AC"], &["2C"], &["3C", "6H"], &["4C", "8S"]],
+            [&["5D"], &[], &[], &[]],
+            &["9D"],
+        )
+    );
+
+    // Cards can't move between waste piles or from foundations
+    assert!(matches!(
+        GameRules::move_card(game.clone(), PileRef::Tableau(0), PileRef::Tableau(1)),
+        Err(Error::InvalidInput { field: "dst", .. })
+    ));
+    assert!(matches!(
+        GameRules::move_card(game.clone(), PileRef::Foundation(0), PileRef::Tableau(1)),
+        Err(Error::InvalidInput { field: "src", .. })
+    ));
+    assert_eq!(
+        GameRules::move_card(game, PileRef::Tableau(1), PileRef::Foundation(0)),
+        Err(Error::InvalidMove {
+            reason: "source pile is empty"
+        })
+    );
+
+    Ok(())
+}
+
+fn strs(v: &[String]) -> Vec<&str> {
+    v.iter().map(String::as_str).collect()
+}
+
+#[test]
+fn test_game_rules_move_card_win() -> Result<()> {
+    let foundation = |n: u8| -> Vec<String> {
+        (0..Rank::N as u8)
+            .map(|i| format!("{:?}C", Rank::Ace.add_wrapping(n).add_wrapping(i * (n + 1))))
+            .collect()
+    };
+    let [f0, f1, f2, mut f3] = [0, 1, 2, 3].map(foundation);
+    let last = f3.pop().unwrap();
+    let game = playing(
+        [&strs(&f0), &strs(&f1), &strs(&f2), &strs(&f3)],
+        [&[], &[], &[], &[]],
+        &[&last],
+    );
+    match GameRules::move_card(game, PileRef::Stock, PileRef::Foundation(3))? {
+        MoveResult::Win(win) => {
+            assert!(win.foundations.iter().all(|f| f.len() == Rank::N));
+            assert!(win
+                .foundations
+                .iter()
+                .all(|f| f.top().unwrap().rank == Rank::King));
+        }
+        MoveResult::Playing(_) => panic!(),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_rules() {
+    assert_eq!(
+        RULES.to_string(),
+        "Calculation
+- foundation (4): builds up by 1, 2, 3, etc. for each pile, wrapping, empty piles stay empty
+- tableau (4): builds in any order, empty piles take any card
+- stock (1): not built on
+the stock is dealt through once"
+    );
+}
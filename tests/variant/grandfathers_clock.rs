@@ -0,0 +1,147 @@
+use solitaire::variant::grandfathers_clock::*;
+use test_util::parse;
+
+fn seeded() -> [Stack; NUM_FOUNDATIONS] {
+    SEEDS.map(|(rank, suit)| {
+        vec![Card {
+            rank,
+            suit,
+            face_up: true,
+        }]
+    })
+}
+
+#[test]
+fn test_game_rules_deal() {
+    let deck: Deck = Card::new_deck();
+    let game = GameRules::deal(deck);
+
+    assert_eq!(game.foundations, seeded());
+    for t in game.tableau.iter() {
+        assert_eq!(t.len(), TABLEAU_DEPTH);
+        assert!(t.iter().all(|c| c.face_up));
+        assert!(t.iter().all(|c| !SEEDS.contains(&(c.rank, c.suit))));
+    }
+}
+
+#[test]
+fn test_game_rules_target() {
+    assert_eq!(GameRules::target(0), Rank::Ace);
+    assert_eq!(GameRules::target(9), Rank::Ten);
+    assert_eq!(GameRules::target(11), Rank::Queen);
+
+    // Each seed is three or four ranks below its hour
+    for (n, (rank, _)) in SEEDS.iter().enumerate() {
+        let target = GameRules::target(n);
+        assert!(rank.add_wrapping(3) == target || rank.add_wrapping(4) == target);
+    }
+
+    assert!(!GameRules::is_complete(&parse::cards(&["XH"]), 0));
+    assert!(!GameRules::is_complete(&parse::cards(&["XH", "JH"]), 0));
+    assert!(GameRules::is_complete(
+        &parse::cards(&["XH", "JH", "QH", "KH", "AH"]),
+        0
+    ));
+}
+
+#[test]
+fn test_game_rules_move_card() -> Result<()> {
+    let mut game = GameRules::deal(Card::new_deck());
+    game.tableau = [
+        parse::cards(&["9S", "AH"]),
+        parse::cards(&["3H"]),
+        parse::cards(&["KS", "JH"]),
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        parse::cards(&["4C"]),
+    ];
+
+    // J♥ onto X♥ at one o'clock
+    let game = match GameRules::move_card(game, PileRef::Tableau(2), PileRef::Foundation(0))? {
+        MoveResult::Playing(game) => game,
+        MoveResult::Win(_) => panic!(),
+    };
+    assert_eq!(game.foundations[0], parse::cards(&["XH", "JH"]));
+    assert_eq!(game.tableau[2], parse::cards(&["KS"]));
+
+    // Wrong suit or rank for the foundation
+    assert_eq!(
+        GameRules::move_card(game.clone(), PileRef::Tableau(1), PileRef::Foundation(3)),
+        Err(Error::InvalidMove {
+            reason: "foundation must be built up in suit"
+        })
+    );
+    // Tableau builds down regardless of suit
+    assert_eq!(
+        GameRules::move_card(game.clone(), PileRef::Tableau(1), PileRef::Tableau(7)),
+        Ok(MoveResult::Playing(PlayingGameState {
+            tableau: {
+                let mut t = game.tableau.clone();
+                t[1] = vec![];
+                t[7] = parse::cards(&["4C", "3H"]);
+                t
+            },
+            ..game.clone()
+        }))
+    );
+    assert_eq!(
+        GameRules::move_card(game.clone(), PileRef::Tableau(7), PileRef::Tableau(1)),
+        Err(Error::InvalidMove {
+            reason: "tableau must be built down"
+        })
+    );
+    // Empty piles take any card
+    assert!(GameRules::move_card(game.clone(), PileRef::Tableau(0), PileRef::Tableau(3)).is_ok());
+    // Cards can't be moved from foundations
+    assert!(matches!(
+        GameRules::move_card(game, PileRef::Foundation(0), PileRef::Tableau(3)),
+        Err(Error::InvalidInput { field: "src", .. })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_game_rules_move_card_win() -> Result<()> {
+    // Build every foundation up to its hour, except the last card for one o'clock
+    let mut foundations = seeded();
+    for (n, f) in foundations.iter_mut().enumerate() {
+        while !GameRules::is_complete(f, n) {
+            let top = *f.top().unwrap();
+            f.push(Card {
+                rank: top.rank.add_wrapping(1),
+                ..top
+            });
+        }
+    }
+    let last = foundations[0].pop().unwrap();
+    let mut tableau = [(); NUM_TABLEAU].map(|_| Stack::new());
+    tableau[0].push(last);
+    assert_eq!(foundations.iter().map(Vec::len).sum::<usize>(), Card::N - 1);
+
+    let game = PlayingGameState {
+        foundations,
+        tableau,
+    };
+    match GameRules::move_card(game, PileRef::Tableau(0), PileRef::Foundation(0))? {
+        MoveResult::Win(win) => {
+            for (n, f) in win.foundations.iter().enumerate() {
+                assert_eq!(f.top().unwrap().rank, GameRules::target(n));
+            }
+        }
+        MoveResult::Playing(_) => panic!(),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_rules() {
+    assert_eq!(
+        RULES.to_string(),
+        "Grandfather's Clock
+- foundation (12): builds up in suit, empty piles stay empty
+- tableau (8): builds down, empty piles take any card"
+    );
+}
@@ -159,6 +159,78 @@ fn test_game_rules_valid_seq_foundation() {
     assert!(!GameRules::valid_seq(p, invalid_overflow.as_slice()));
 }
 
+/// Test the relaxed sequence validation, which allows any face up run on the tableau
+#[test]
+fn test_game_rules_valid_seq_relaxed() {
+    let run = parse::cards(&["8H", "7D", "KS"]);
+    assert!(!GameRules::valid_seq_with(
+        SeqStrategy::Strict,
+        PileRef::Tableau(0),
+        &run
+    ));
+    assert!(GameRules::valid_seq_with(
+        SeqStrategy::Relaxed,
+        PileRef::Tableau(0),
+        &run
+    ));
+    // Still only face up cards
+    assert!(!GameRules::valid_seq_with(
+        SeqStrategy::Relaxed,
+        PileRef::Tableau(0),
+        &parse::cards(&["#8H", "7S"])
+    ));
+    // Foundations are unaffected
+    assert!(!GameRules::valid_seq_with(
+        SeqStrategy::Relaxed,
+        PileRef::Foundation(0),
+        &parse::cards(&["6D", "7D", "8H"])
+    ));
+}
+
+/// Test moving a run that isn't a valid sequence, but is valid to place on the destination
+#[test]
+fn test_game_rules_move_cards_relaxed() {
+    let game = parse::playing_state(
+        "tableau 1: #2D 8H 7D 4S
+        tableau 2: 9S
+        tableau 3: 9D",
+    );
+
+    assert_eq!(
+        GameRules::move_cards(game.clone(), PileRef::Tableau(0), 3, PileRef::Tableau(1)),
+        Err(Error::InvalidMove {
+            reason: "src sequence is invalid"
+        })
+    );
+    assert_eq!(
+        GameRules::move_cards_with(
+            SeqStrategy::Relaxed,
+            game.clone(),
+            PileRef::Tableau(0),
+            3,
+            PileRef::Tableau(1)
+        ),
+        Ok(MoveResult::Playing(parse::playing_state(
+            "tableau 1: 2D
+            tableau 2: 9S 8H 7D 4S
+            tableau 3: 9D",
+        )))
+    );
+    // The joint with the destination must still be valid
+    assert_eq!(
+        GameRules::move_cards_with(
+            SeqStrategy::Relaxed,
+            game,
+            PileRef::Tableau(0),
+            3,
+            PileRef::Tableau(2)
+        ),
+        Err(Error::InvalidMove {
+            reason: "dst sequence is invalid"
+        })
+    );
+}
+
 /// Test basic input validation when moving cards,
 /// e.g. making sure you can't take 0 cards,
 /// or trying to move cards to the stock, etc.
@@ -446,3 +518,202 @@ fn test_game_rules_move_cards_win() -> Result<()> {
 
     Ok(())
 }
+
+/// Test the textual description of a game state
+#[test]
+fn test_describe_state() {
+    let game = parse::playing_state(
+        "stock: #KC #AH
+        talon: 2D
+        foundation 1: AS 2S
+        tableau 3: #9C #3H 7S 6H",
+    );
+
+    let description = describe_state(&GameStateOption::from(game));
+
+    let tableau = &description.piles[8];
+    assert_eq!(tableau.pile, PileRef::Tableau(2));
+    assert_eq!(tableau.face_down, 2);
+    assert_eq!(tableau.face_up, parse::cards(&["7S", "6H"]));
+
+    assert_eq!(
+        description.to_string(),
+        "stock: 2 face down
+talon: 2♦
+foundation 1: A♠ 2♠
+foundation 2: empty
+foundation 3: empty
+foundation 4: empty
+tableau 1: empty
+tableau 2: empty
+tableau 3: 2 face down, 7♠ 6♥
+tableau 4: empty
+tableau 5: empty
+tableau 6: empty
+tableau 7: empty"
+    );
+}
+
+/// Test the status of each card for the card-counting overlay
+#[test]
+fn test_count_cards() {
+    let game = parse::playing_state(
+        "stock: #KC #AH
+        talon: 2D 5C
+        foundation 1: AS 2S
+        tableau 3: #9C #3H 7S 6H",
+    );
+
+    let count = count_cards(&GameStateOption::from(game));
+    assert_eq!(
+        count.get(FrenchSuit::Spades, Rank::Two),
+        CardStatus::Foundation
+    );
+    assert_eq!(
+        count.get(FrenchSuit::Diamonds, Rank::Two),
+        CardStatus::Visible
+    );
+    assert_eq!(
+        count.get(FrenchSuit::Hearts, Rank::Six),
+        CardStatus::Visible
+    );
+    assert_eq!(count.get(FrenchSuit::Clubs, Rank::Nine), CardStatus::Unseen);
+    assert_eq!(count.get(FrenchSuit::Clubs, Rank::King), CardStatus::Unseen);
+    assert_eq!(count.count(CardStatus::Foundation), 2);
+    assert_eq!(count.count(CardStatus::Visible), 4);
+    // Cards that aren't anywhere in the state are unseen too
+    assert_eq!(count.count(CardStatus::Unseen), Card::N - 6);
+}
+
+/// Test generating a mid-game state from random legal moves
+#[test]
+fn test_random_playing_state() {
+    use rand::{rngs::SmallRng, SeedableRng};
+    use test_util::random;
+
+    let game = random::random_playing_state(&mut SmallRng::seed_from_u64(42), 50);
+
+    // Same seed, same game
+    assert_eq!(
+        game,
+        random::random_playing_state(&mut SmallRng::seed_from_u64(42), 50)
+    );
+
+    let mut cards: Vec<_> = game
+        .tableau
+        .iter()
+        .chain(game.foundations.iter())
+        .chain([&game.stock, &game.talon])
+        .flatten()
+        .map(|c| (c.suit as usize, c.rank as usize))
+        .collect();
+    cards.sort();
+    cards.dedup();
+    assert_eq!(cards.len(), Card::N);
+}
+
+/// Test finding the movable run at the top of a tableau pile
+#[test]
+fn test_movable_prefix() {
+    assert_eq!(movable_prefix(&parse::cards(&[])), 0);
+    assert_eq!(movable_prefix(&parse::cards(&["#KS"])), 0);
+    assert_eq!(movable_prefix(&parse::cards(&["#KS", "QH"])), 1);
+    assert_eq!(movable_prefix(&parse::cards(&["#KS", "9C", "8D", "7S"])), 3);
+    assert_eq!(movable_prefix(&parse::cards(&["KS", "QH", "JC", "XD"])), 4);
+
+    // The run is broken by a card of the same color
+    assert_eq!(movable_prefix(&parse::cards(&["9C", "8S", "7D", "6C"])), 3);
+    // Or by a gap in ranks
+    assert_eq!(movable_prefix(&parse::cards(&["9C", "7D", "6C"])), 2);
+}
+
+/// Test the relaxed movable run, which is every face up card
+#[test]
+fn test_movable_prefix_relaxed() {
+    let pile = parse::cards(&["#KS", "9C", "7D", "6C"]);
+    assert_eq!(movable_prefix_with(SeqStrategy::Strict, &pile), 2);
+    assert_eq!(movable_prefix_with(SeqStrategy::Relaxed, &pile), 3);
+    assert_eq!(
+        movable_prefix_with(SeqStrategy::Relaxed, &parse::cards(&["#KS"])),
+        0
+    );
+}
+
+/// Test moving a card to the foundations only
+#[test]
+fn test_game_rules_move_to_foundation() -> Result<()> {
+    let game = parse::playing_state(
+        "talon: AH
+        foundation 2: AS
+        tableau 1: #KC 2S
+        tableau 2: 9D",
+    );
+
+    // The first empty foundation takes the Ace
+    let game = match GameRules::move_to_foundation(game, PileRef::Talon)? {
+        MoveResult::Playing(game) => game,
+        MoveResult::Win(_) => panic!(),
+    };
+    assert_eq!(game.foundations[0], parse::cards(&["AH"]));
+    assert!(game.talon.is_empty());
+
+    // The Two goes onto its Ace, wherever it is
+    let game = match GameRules::move_to_foundation(game, PileRef::Tableau(0))? {
+        MoveResult::Playing(game) => game,
+        MoveResult::Win(_) => panic!(),
+    };
+    assert_eq!(game.foundations[1], parse::cards(&["AS", "2S"]));
+    assert_eq!(game.tableau[0], parse::cards(&["KC"]));
+
+    // Cards with no place on the foundations aren't moved to the tableau
+    assert_eq!(
+        GameRules::move_to_foundation(game.clone(), PileRef::Tableau(1)),
+        Err(Error::InvalidMove {
+            reason: "no foundation accepts the card"
+        })
+    );
+    assert!(matches!(
+        GameRules::move_to_foundation(game.clone(), PileRef::Foundation(0)),
+        Err(Error::InvalidInput { .. })
+    ));
+    assert!(matches!(
+        GameRules::move_to_foundation(game, PileRef::Stock),
+        Err(Error::InvalidInput { .. })
+    ));
+
+    Ok(())
+}
+
+/// Test playing the safe opening moves
+#[test]
+fn test_game_rules_play_opening() {
+    let game = parse::playing_state(
+        "talon: AC
+        tableau 1: #2H AS
+        tableau 2: #AH 2S
+        tableau 3: #3D 9D",
+    );
+
+    // Each move reveals the card for the next
+    let game = GameRules::play_opening(game);
+    assert_eq!(game.foundations[0], parse::cards(&["AS", "2S"]));
+    assert_eq!(game.foundations[1], parse::cards(&["AH", "2H"]));
+    assert!(game.tableau[0].is_empty());
+    assert!(game.tableau[1].is_empty());
+    assert_eq!(game.tableau[2], parse::cards(&["#3D", "9D"]));
+    // Only the tableau is played from
+    assert_eq!(game.talon, parse::cards(&["AC"]));
+}
+
+#[test]
+fn test_rules() {
+    assert_eq!(
+        RULES.to_string(),
+        "Klondike
+- tableau (7): builds down in alternating colors, empty piles take K
+- foundation (4): builds up in suit, empty piles take A
+- stock (1): not built on
+- talon (1): not built on
+the talon can be turned over any number of times"
+    );
+}
@@ -0,0 +1,61 @@
+use proptest::prelude::*;
+use solitaire::{variant::klondike::*, GameState};
+use test_util::{random, strategy};
+
+/// Retrieves every card in the state as a (suit, rank) pair, sorted
+fn cards(state: &GameStateOption) -> Vec<(usize, usize)> {
+    let pile_refs = [PileRef::Stock, PileRef::Talon]
+        .into_iter()
+        .chain((0..NUM_FOUNDATIONS).map(PileRef::Foundation))
+        .chain((0..NUM_TABLEAU).map(PileRef::Tableau));
+    let mut cards: Vec<_> = pile_refs
+        .filter_map(|p| state.get_stack(p))
+        .flatten()
+        .map(|c| (c.suit as usize, c.rank as usize))
+        .collect();
+    cards.sort();
+    cards
+}
+
+fn full_deck() -> Vec<(usize, usize)> {
+    cards(&GameStateOption::from(InitialGameState::from(
+        Card::new_deck(),
+    )))
+}
+
+proptest! {
+    /// Dealing never loses or duplicates a card
+    #[test]
+    fn prop_deal_all_keeps_cards(deck in strategy::deck()) {
+        let game = GameRules::deal_all(InitialGameState::from(deck));
+        prop_assert_eq!(cards(&GameStateOption::from(game)), full_deck());
+    }
+
+    /// Moving never loses or duplicates a card
+    #[test]
+    fn prop_move_keeps_cards(game in strategy::playing_state(), m in strategy::any_move()) {
+        let before = cards(&GameStateOption::from(game.clone()));
+        prop_assert_eq!(&before, &full_deck());
+        if let Ok(result) = m.apply(game) {
+            prop_assert_eq!(cards(&GameStateOption::from(result)), before);
+        }
+    }
+
+    /// The top card of every tableau pile is always face up after a move
+    #[test]
+    fn prop_move_reveals_tableau(game in strategy::playing_state(), m in strategy::any_move()) {
+        if let Ok(MoveResult::Playing(game)) = m.apply(game) {
+            for pile in game.tableau.iter() {
+                prop_assert!(pile.last().into_iter().all(|c| c.face_up));
+            }
+        }
+    }
+
+    /// Every move from legal_moves is accepted, and gives the state it was listed with
+    #[test]
+    fn prop_legal_moves_are_accepted(game in strategy::playing_state()) {
+        for (m, next) in random::legal_moves(&game) {
+            prop_assert_eq!(m.apply(game.clone()), Ok(MoveResult::Playing(next)));
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use solitaire::variant::osmosis::*;
+use test_util::parse;
+
+fn playing(
+    reserve: [&[&str]; NUM_RESERVE],
+    foundations: [&[&str]; NUM_FOUNDATIONS],
+    talon: &[&str],
+) -> PlayingGameState {
+    PlayingGameState {
+        reserve: reserve.map(parse::cards),
+        foundations: foundations.map(parse::cards),
+        stock: vec![],
+        talon: parse::cards(talon),
+    }
+}
+
+#[test]
+fn test_game_rules_deal() {
+    let deck: Deck = Card::new_deck();
+    let game = GameRules::deal(deck);
+
+    for r in game.reserve.iter() {
+        let (face_down, face_up) = r.split_face_up();
+        assert_eq!((face_down.len(), face_up.len()), (RESERVE_DEPTH - 1, 1));
+    }
+    assert_eq!(game.foundations[0].len(), 1);
+    assert!(game.foundations[0][0].face_up);
+    assert!(game.foundations[1..].iter().all(|f| f.is_empty()));
+    assert_eq!(game.stock.len(), Card::N - NUM_RESERVE * RESERVE_DEPTH - 1);
+    assert!(game.talon.is_empty());
+}
+
+#[test]
+fn test_game_rules_draw_stock() -> Result<()> {
+    let mut game = playing([&[], &[], &[], &[]], [&["5H"], &[], &[], &[]], &[]);
+    game.stock = parse::cards(&["#AS", "#2S", "#3S", "#4S"]);
+
+    let game = GameRules::draw_stock(game, DRAW_N)?;
+    assert_eq!(game.stock, parse::cards(&["#AS"]));
+    assert_eq!(game.talon, parse::cards(&["2S", "3S", "4S"]));
+    let game = GameRules::draw_stock(game, DRAW_N)?;
+    let game = GameRules::draw_stock(game, DRAW_N)?;
+    assert_eq!(game.stock, parse::cards(&["#AS", "#4S", "#3S", "#2S"]));
+    assert!(game.talon.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_game_rules_accepts() {
+    let game = playing(
+        [&[], &[], &[], &[]],
+        [&["5H", "9H", "KH"], &["5S", "KS"], &[], &[]],
+        &[],
+    );
+    let f = &game.foundations;
+
+    // Any heart on the first foundation
+    assert!(GameRules::accepts(f, 0, &parse::card("2H")));
+    assert!(!GameRules::accepts(f, 0, &parse::card("2S")));
+    // Spades only once the rank is on the hearts
+    assert!(GameRules::accepts(f, 1, &parse::card("9S")));
+    assert!(!GameRules::accepts(f, 1, &parse::card("2S")));
+    assert!(!GameRules::accepts(f, 1, &parse::card("9C")));
+    // Empty foundations take the base rank in an unused suit
+    assert!(GameRules::accepts(f, 2, &parse::card("5C")));
+    assert!(GameRules::accepts(f, 3, &parse::card("5D")));
+    assert!(!GameRules::accepts(f, 2, &parse::card("6C")));
+    assert!(!GameRules::accepts(f, 2, &parse::card("5S")));
+}
+
+#[test]
+fn test_game_rules_check_foundation() {
+    let game = playing(
+        [&[], &[], &[], &[]],
+        [&["5H", "9H", "KH"], &["5S", "KS"], &[], &[]],
+        &[],
+    );
+    let f = &game.foundations;
+
+    assert_eq!(
+        GameRules::check_foundation(f, 0, &parse::card("2S")),
+        Err(Error::InvalidMove {
+            reason: "foundation must be built in suit"
+        })
+    );
+    assert_eq!(
+        GameRules::check_foundation(f, 1, &parse::card("9C")),
+        Err(Error::InvalidMove {
+            reason: "foundation must be built in suit"
+        })
+    );
+    assert_eq!(
+        GameRules::check_foundation(f, 1, &parse::card("2S")),
+        Err(Error::InvalidMove {
+            reason: "rank must already be on the foundation above"
+        })
+    );
+    assert_eq!(
+        GameRules::check_foundation(f, 2, &parse::card("6C")),
+        Err(Error::InvalidMove {
+            reason: "empty foundation must start with the base card's rank"
+        })
+    );
+    assert_eq!(
+        GameRules::check_foundation(f, 2, &parse::card("5S")),
+        Err(Error::InvalidMove {
+            reason: "suit already has a foundation"
+        })
+    );
+    assert_eq!(
+        GameRules::check_foundation(&[vec![], vec![], vec![], vec![]], 0, &parse::card("5S")),
+        Err(Error::InvalidMove {
+            reason: "first foundation has no base card"
+        })
+    );
+}
+
+#[test]
+fn test_game_rules_move_to_foundation_skipping_empty() -> Result<()> {
+    // A base card can start the last foundation while the one above it is still empty
+    let game = playing([&["5D"], &[], &[], &[]], [&["5H"], &["5S"], &[], &[]], &[]);
+    let game = match GameRules::move_to_foundation(game, PileRef::Tableau(0), 3)? {
+        MoveResult::Playing(game) => game,
+        MoveResult::Win(_) => panic!(),
+    };
+    assert_eq!(
+        game.foundations,
+        [&["5H"][..], &["5S"], &[], &["5D"]].map(parse::cards)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_game_rules_move_to_foundation() -> Result<()> {
+    let game = playing(
+        [&["#4C", "9S"], &[], &[], &[]],
+        [&["5H", "9H"], &["5S"], &[], &[]],
+        &["KH", "KS"],
+    );
+
+    // The K♠ isn't on the hearts yet
+    assert_eq!(
+        GameRules::move_to_foundation(game.clone(), PileRef::Talon, 1),
+        Err(Error::InvalidMove {
+            reason: "rank must already be on the foundation above"
+        })
+    );
+
+    // But the 9♠ is, which unseals the 4♣ underneath
+    let game = match GameRules::move_to_foundation(game, PileRef::Tableau(0), 1)? {
+        MoveResult::Playing(game) => game,
+        MoveResult::Win(_) => panic!(),
+    };
+    assert_eq!(
+        game,
+        playing(
+            [&["4C"], &[], &[], &[]],
+            [&["5H", "9H"], &["5S", "9S"], &[], &[]],
+            &["KH", "KS"],
+        )
+    );
+
+    assert!(matches!(
+        GameRules::move_to_foundation(game.clone(), PileRef::Stock, 0),
+        Err(Error::InvalidInput { field: "src", .. })
+    ));
+    assert!(matches!(
+        GameRules::move_to_foundation(game, PileRef::Talon, NUM_FOUNDATIONS),
+        Err(Error::InvalidInput { field: "dst", .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_game_rules_move_to_foundation_win() -> Result<()> {
+    let mut foundations = [(); NUM_FOUNDATIONS].map(|_| Stack::new());
+    for c in Card::new_deck() {
+        foundations[FrenchSuit::iter().position(|s| s == c.suit).unwrap()].push(c);
+    }
+    let last = foundations[3].pop().unwrap();
+    let game = PlayingGameState {
+        reserve: [(); NUM_RESERVE].map(|_| Stack::new()),
+        foundations,
+        stock: vec![],
+        talon: vec![last],
+    };
+    assert!(matches!(
+        GameRules::move_to_foundation(game, PileRef::Talon, 3)?,
+        MoveResult::Win(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_rules() {
+    assert_eq!(
+        RULES.to_string(),
+        "Osmosis
+- reserve (4): not built on
+- foundation (4): builds with ranks already on the previous pile in suit, empty piles take the base card's rank
+- stock (1): not built on
+- talon (1): not built on
+the talon can be turned over any number of times"
+    );
+}
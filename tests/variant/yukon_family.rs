@@ -0,0 +1,130 @@
+use solitaire::rules::{Build, Matching};
+use solitaire::variant::yukon_family::*;
+use test_util::parse;
+
+#[test]
+fn test_rules_deal() {
+    let deck: Deck = Card::new_deck();
+    let game = Rules::<Yukon>::deal(deck);
+
+    for (n, t) in game.tableau.iter().enumerate() {
+        let (face_down, face_up) = t.split_face_up();
+        assert_eq!(face_down.len(), n);
+        assert_eq!(face_up.len(), if n == 0 { 1 } else { NUM_FACE_UP });
+    }
+    assert_eq!(game.tableau.iter().map(Vec::len).sum::<usize>(), Card::N);
+    assert!(game.stock.is_empty() && game.talon.is_empty());
+}
+
+#[test]
+fn test_rules_builds_on() {
+    let c = parse::card;
+
+    assert!(Rules::<Yukon>::builds_on(&c("8S"), &c("7H")));
+    assert!(!Rules::<Yukon>::builds_on(&c("8S"), &c("7C")));
+    assert!(!Rules::<Yukon>::builds_on(&c("8S"), &c("9H")));
+
+    assert!(Rules::<Russian>::builds_on(&c("8S"), &c("7S")));
+    assert!(!Rules::<Russian>::builds_on(&c("8S"), &c("7H")));
+    assert!(!Rules::<Russian>::builds_on(&c("8S"), &c("9S")));
+
+    assert!(Rules::<Alaska>::builds_on(&c("8S"), &c("7S")));
+    assert!(Rules::<Alaska>::builds_on(&c("8S"), &c("9S")));
+    assert!(!Rules::<Alaska>::builds_on(&c("8S"), &c("9H")));
+    assert!(!Rules::<Alaska>::builds_on(&c("8S"), &c("XS")));
+}
+
+#[test]
+fn test_rules_move_cards() -> Result<()> {
+    let game = parse::playing_state(
+        "tableau 1: #2D 8S
+        tableau 2: #KC 7H 3C 9D
+        tableau 3: 7S
+        foundation 1: AH",
+    );
+
+    // Any face up cards can be moved, as long as the bottom one fits
+    let expected = parse::playing_state(
+        "tableau 1: #2D 8S 7H 3C 9D
+        tableau 2: KC
+        tableau 3: 7S
+        foundation 1: AH",
+    );
+    assert_eq!(
+        Rules::<Yukon>::move_cards(game.clone(), PileRef::Tableau(1), 3, PileRef::Tableau(0)),
+        Ok(MoveResult::Playing(expected))
+    );
+    assert_eq!(
+        Rules::<Russian>::move_cards(game.clone(), PileRef::Tableau(1), 3, PileRef::Tableau(0)),
+        Err(Error::InvalidMove {
+            reason: "dst sequence is invalid"
+        })
+    );
+    assert!(Rules::<Russian>::move_cards(
+        game.clone(),
+        PileRef::Tableau(2),
+        1,
+        PileRef::Tableau(0)
+    )
+    .is_ok());
+
+    // Face down cards can't be moved
+    assert_eq!(
+        Rules::<Yukon>::move_cards(game.clone(), PileRef::Tableau(1), 4, PileRef::Tableau(3)),
+        Err(Error::InvalidMove {
+            reason: "cannot move face down cards"
+        })
+    );
+    // Only a King can be moved to a space
+    assert_eq!(
+        Rules::<Alaska>::move_cards(game.clone(), PileRef::Tableau(1), 1, PileRef::Tableau(3)),
+        Err(Error::InvalidMove {
+            reason: "dst sequence is invalid"
+        })
+    );
+    // Foundations take a single card at a time
+    assert!(matches!(
+        Rules::<Yukon>::move_cards(game, PileRef::Tableau(1), 2, PileRef::Foundation(1)),
+        Err(Error::InvalidInput {
+            field: "take_n",
+            ..
+        })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_rules_move_cards_win() -> Result<()> {
+    let mut game = parse::playing_state("tableau 1: KD");
+    for (f, suit) in game.foundations.iter_mut().zip(FrenchSuit::iter()) {
+        *f = Rank::VALUES
+            .iter()
+            .rev()
+            .map(|&rank| Card {
+                rank,
+                suit,
+                face_up: true,
+            })
+            .filter(|c| c.rank != Rank::King || c.suit != FrenchSuit::Diamonds)
+            .collect();
+    }
+
+    assert!(matches!(
+        Rules::<Alaska>::move_cards(game, PileRef::Tableau(0), 1, PileRef::Foundation(3))?,
+        MoveResult::Win(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_rules() {
+    assert_eq!(
+        Rules::<Yukon>::RULES.to_string(),
+        "Yukon
+- tableau (7): builds down in alternating colors, empty piles take K
+- foundation (4): builds up in suit, empty piles take A"
+    );
+    assert_eq!(Rules::<Russian>::RULES.piles[0].matching, Matching::Suit);
+    assert_eq!(Rules::<Alaska>::RULES.piles[0].build, Build::UpOrDown);
+}
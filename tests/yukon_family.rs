@@ -0,0 +1,2 @@
+#[path = "variant/yukon_family.rs"]
+pub mod yukon_family;